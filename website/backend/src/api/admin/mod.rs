@@ -0,0 +1,5 @@
+//! Admin-only maintenance endpoints, not exposed anywhere in the regular
+//! search/document browsing flow.
+
+mod purge_search_cache;
+pub use purge_search_cache::purge_search_cache;
@@ -0,0 +1,9 @@
+use crate::db_utils::manticore_utils::purge_cache;
+
+/// Drops rows from `search_manticore_cache` matching `query_substring`,
+/// `older_than_seconds`, or both, so an admin can force a collection's
+/// search results to be refreshed right after a reindex or a document
+/// removal instead of waiting out the TTL.
+pub async fn purge_search_cache(query_substring: Option<String>, older_than_seconds: Option<u32>) -> anyhow::Result<()> {
+    purge_cache(query_substring, older_than_seconds).await
+}
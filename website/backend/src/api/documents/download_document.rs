@@ -14,6 +14,40 @@ pub struct BlobInfo {
     pub blob_size_bytes: u64,
     pub s3_path: String,
     pub stored_in_clickhouse: bool,
+    #[serde(default)]
+    pub compression: String,
+}
+
+/// The encoding a stored blob is compressed with. Persisted on `BlobInfo`
+/// as a plain string column (`compression`) so it round-trips through
+/// ClickHouse without a custom enum type; `None` means the stored bytes
+/// are already plaintext.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlobCompression {
+    None,
+    Zstd,
+    Gzip,
+    Brotli,
+}
+
+impl BlobCompression {
+    pub fn from_db_value(value: &str) -> Self {
+        match value {
+            "zstd" => BlobCompression::Zstd,
+            "gzip" => BlobCompression::Gzip,
+            "brotli" => BlobCompression::Brotli,
+            _ => BlobCompression::None,
+        }
+    }
+
+    pub fn as_db_value(&self) -> &'static str {
+        match self {
+            BlobCompression::None => "none",
+            BlobCompression::Zstd => "zstd",
+            BlobCompression::Gzip => "gzip",
+            BlobCompression::Brotli => "brotli",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
@@ -23,9 +57,27 @@ pub struct BlobValue {
     pub blob_length: u64,
 }
 
+/// An HTTP-style byte range request: `start` is inclusive, `end` (if
+/// present) is inclusive as well. `end: None` means "to the end of the
+/// object", mirroring the `bytes=start-` form of the `Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: u64,
+    pub end: Option<u64>,
+}
+
+/// The byte range actually satisfied for a stream, always resolved to a
+/// concrete inclusive `[start, end]` pair once the object size is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SatisfiedRange {
+    pub start: u64,
+    pub end: u64,
+    pub total_size: u64,
+}
+
 async fn get_document_s3_blob_download_path(document_identifier: DocumentIdentifier) -> anyhow::Result< BlobInfo> {
     let client = get_clickhouse_client();
-    let query = "SELECT blob_size_bytes, s3_path, stored_in_clickhouse FROM blobs WHERE collection_dataset = ? AND blob_hash = ? LIMIT 1";
+    let query = "SELECT blob_size_bytes, s3_path, stored_in_clickhouse, compression FROM blobs WHERE collection_dataset = ? AND blob_hash = ? LIMIT 1";
     let query = client.query(query).bind(&document_identifier.collection_dataset).bind(&document_identifier.file_hash);
     let result = query.fetch_all::<BlobInfo>().await?;
     if let Some(blob_info) = result.into_iter().next() {
@@ -60,16 +112,39 @@ pub async fn get_blob_filename(document_identifier: DocumentIdentifier) -> anyho
 }
 
 
-pub async fn get_document_content_stream(document_identifier: DocumentIdentifier) -> anyhow::Result<(usize, Pin<Box<
-dyn futures::Stream<Item = anyhow::Result<bytes::Bytes>> + Send + 'static>>)> {
+type ContentStream = Pin<Box<dyn futures::Stream<Item = anyhow::Result<bytes::Bytes>> + Send + 'static>>;
+
+pub async fn get_document_content_stream(document_identifier: DocumentIdentifier, range: Option<Range>) -> anyhow::Result<(usize, Option<SatisfiedRange>, ContentStream)> {
 
     let blob_info = get_document_s3_blob_download_path(document_identifier.clone()).await?;
+    let compression = BlobCompression::from_db_value(&blob_info.compression);
+
     if blob_info.stored_in_clickhouse {
         tracing::info!("Downloading document from clickhouse");
         let blob_value = get_document_blob_content_from_clickhouse(document_identifier.clone()).await?;
-        let data = blob_value.blob_value;
-        let data = anyhow::Ok(bytes::Bytes::from(data));
-        return Ok((blob_value.blob_length as usize, Box::pin(futures::stream::iter([data]))))
+
+        // The whole buffer is already in memory, so decompress eagerly and
+        // then apply the range against the plaintext bytes.
+        let data = if compression == BlobCompression::None {
+            blob_value.blob_value
+        } else {
+            decompress_buffer(blob_value.blob_value, compression).await?
+        };
+        let total_size = data.len() as u64;
+
+        let Some(range) = range else {
+            let data = anyhow::Ok(bytes::Bytes::from(data));
+            return Ok((total_size as usize, None, Box::pin(futures::stream::iter([data]))));
+        };
+
+        let end = range.end.unwrap_or(total_size.saturating_sub(1)).min(total_size.saturating_sub(1));
+        if range.start > end || range.start >= total_size {
+            anyhow::bail!("Requested range {}-{:?} is not satisfiable for a {} byte object", range.start, range.end, total_size);
+        }
+        let slice = data[range.start as usize..=end as usize].to_vec();
+        let satisfied = SatisfiedRange { start: range.start, end, total_size };
+        let data = anyhow::Ok(bytes::Bytes::from(slice));
+        return Ok((total_size as usize, Some(satisfied), Box::pin(futures::stream::iter([data]))));
     }
 
     tracing::info!("Downloading document from s3");
@@ -80,13 +155,75 @@ dyn futures::Stream<Item = anyhow::Result<bytes::Bytes>> + Send + 'static>>)> {
     let base_url = s3_endpoint.parse::<minio::s3::http::BaseUrl>().context("Failed to parse s3 endpoint")?;
     let static_provider = minio::s3::creds::StaticProvider::new("hoover4", "hoover4-secret", None);
     let client = minio::s3::Client::new(base_url, Some(Box::new(static_provider)), None, None).context("Failed to create s3 client")?;
-    let object = client.get_object(s3_bucket, s3_path).send().await.context("Failed to get object")?;
-    let object_size = object.object_size as usize;
-    assert_eq!(object_size, blob_info.blob_size_bytes as usize);
-    let (stream, _size) = object.content.to_stream().await.context("Failed to get object stream")?;
 
+    let stored_size = blob_info.blob_size_bytes;
+
+    // Seeking into a compressed object on disk is not meaningful, so a
+    // compressed blob is always streamed (and decompressed) in full; range
+    // requests only apply to uncompressed blobs.
+    if compression != BlobCompression::None {
+        let object = client.get_object(s3_bucket, s3_path).send().await.context("Failed to get object")?;
+        let object_size = object.object_size as u64;
+        if object_size != stored_size {
+            anyhow::bail!("Blob size mismatch: expected {} bytes, got {}", stored_size, object_size);
+        }
+        let (stream, _size) = object.content.to_stream().await.context("Failed to get object stream")?;
+        let stream = stream.map_err(|x| anyhow::Error::from(x));
+        let decoded = decompress_stream(Box::pin(stream), compression);
+        return Ok((stored_size as usize, None, decoded));
+    }
+
+    let Some(range) = range else {
+        let object = client.get_object(s3_bucket, s3_path).send().await.context("Failed to get object")?;
+        let object_size = object.object_size as u64;
+        if object_size != stored_size {
+            anyhow::bail!("Blob size mismatch: expected {} bytes, got {}", stored_size, object_size);
+        }
+        let (stream, _size) = object.content.to_stream().await.context("Failed to get object stream")?;
+        let stream2 = stream.map_err(|x| anyhow::Error::from(x));
+        return Ok((object_size as usize, None, Box::pin(stream2)));
+    };
+
+    let end = range.end.unwrap_or(stored_size.saturating_sub(1)).min(stored_size.saturating_sub(1));
+    if range.start > end || range.start >= stored_size {
+        anyhow::bail!("Requested range {}-{:?} is not satisfiable for a {} byte object", range.start, range.end, stored_size);
+    }
+    let object = client.get_object(s3_bucket, s3_path)
+        .offset(range.start)
+        .length(end - range.start + 1)
+        .send().await.context("Failed to get ranged object")?;
+    let (stream, _size) = object.content.to_stream().await.context("Failed to get object stream")?;
     let stream2 = stream.map_err(|x| anyhow::Error::from(x));
+    let satisfied = SatisfiedRange { start: range.start, end, total_size: stored_size };
 
-    Ok((object_size, Box::pin(stream2)))
+    Ok((stored_size as usize, Some(satisfied), Box::pin(stream2)))
+}
 
+/// Decompresses an already-buffered blob in memory.
+async fn decompress_buffer(data: Vec<u8>, compression: BlobCompression) -> anyhow::Result<Vec<u8>> {
+    let stream = futures::stream::iter([anyhow::Ok(bytes::Bytes::from(data))]);
+    let mut decoded = decompress_stream(Box::pin(stream), compression);
+    let mut buffer = Vec::new();
+    while let Some(chunk) = decoded.next().await {
+        buffer.extend_from_slice(&chunk?);
+    }
+    Ok(buffer)
+}
+
+/// Wraps a byte stream in the streaming decompressor matching `compression`,
+/// so large objects are never fully buffered just to decode them.
+fn decompress_stream(stream: ContentStream, compression: BlobCompression) -> ContentStream {
+    if compression == BlobCompression::None {
+        return stream;
+    }
+
+    let io_stream = stream.map(|item| item.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)));
+    let reader = tokio::io::BufReader::new(tokio_util::io::StreamReader::new(io_stream));
+
+    match compression {
+        BlobCompression::Zstd => Box::pin(tokio_util::io::ReaderStream::new(async_compression::tokio::bufread::ZstdDecoder::new(reader)).map_err(anyhow::Error::from)),
+        BlobCompression::Gzip => Box::pin(tokio_util::io::ReaderStream::new(async_compression::tokio::bufread::GzipDecoder::new(reader)).map_err(anyhow::Error::from)),
+        BlobCompression::Brotli => Box::pin(tokio_util::io::ReaderStream::new(async_compression::tokio::bufread::BrotliDecoder::new(reader)).map_err(anyhow::Error::from)),
+        BlobCompression::None => unreachable!(),
+    }
 }
\ No newline at end of file
@@ -0,0 +1,226 @@
+//! Server-side syntax highlighting for source-code document previews.
+//!
+//! Not a real syntect integration (no syntax-definition/theme crate
+//! dependency here): a small per-language lexer classifies comments,
+//! strings, numbers and keywords over the whole file, the same kind of
+//! lightweight approach the frontend's snippet lexer already uses for
+//! cropped search-hit fragments, just run over the full text and split back
+//! out by line.
+
+use std::collections::HashSet;
+
+use common::{
+    code_highlight::{CodeHighlightLine, CodeHighlightResponse, CodeToken, CodeTokenClass, DocumentType},
+    search_result::DocumentIdentifier,
+};
+use futures::StreamExt;
+
+use crate::api::documents::{download_document::get_document_content_stream, get_file_path::get_file_path, get_pdf_to_html_conversion::get_document_type_is_pdf};
+
+/// Hard cap on how much of a file gets tokenized and sent to the browser,
+/// so an oversized log (or a binary file misdetected as text) can't stall
+/// the viewer or blow up the response size.
+const MAX_CODE_HIGHLIGHT_BYTES: usize = 2 * 1024 * 1024;
+
+struct LanguageRules {
+    name: &'static str,
+    line_comment: Option<&'static str>,
+    block_comment: Option<(&'static str, &'static str)>,
+    string_delims: &'static [char],
+    keywords: &'static [&'static str],
+}
+
+const RUST_KEYWORDS: &[&str] = &["fn", "let", "mut", "pub", "use", "struct", "enum", "impl", "trait", "for", "in", "while", "loop", "if", "else", "match", "return", "break", "continue", "true", "false", "self", "Self", "mod", "crate", "as", "const", "static", "async", "await", "move", "ref", "where", "unsafe", "type", "dyn", "extern", "super"];
+const PYTHON_KEYWORDS: &[&str] = &["def", "class", "import", "from", "as", "if", "elif", "else", "for", "while", "return", "yield", "try", "except", "finally", "with", "lambda", "None", "True", "False", "pass", "break", "continue", "global", "nonlocal", "assert", "del", "raise", "not", "and", "or", "in", "is", "async", "await"];
+const JS_KEYWORDS: &[&str] = &["function", "const", "let", "var", "if", "else", "for", "while", "return", "class", "extends", "new", "this", "typeof", "instanceof", "import", "export", "from", "as", "async", "await", "try", "catch", "finally", "throw", "switch", "case", "default", "break", "continue", "null", "undefined", "true", "false", "of", "in", "yield", "delete", "void"];
+const GO_KEYWORDS: &[&str] = &["func", "package", "import", "var", "const", "type", "struct", "interface", "map", "chan", "go", "defer", "select", "case", "switch", "if", "else", "for", "range", "return", "break", "continue", "default", "fallthrough", "nil", "true", "false"];
+const C_LIKE_KEYWORDS: &[&str] = &["int", "char", "float", "double", "void", "if", "else", "for", "while", "do", "switch", "case", "default", "break", "continue", "return", "struct", "class", "public", "private", "protected", "static", "final", "const", "new", "this", "true", "false", "null", "enum", "namespace", "template", "virtual", "override", "typedef", "include", "define"];
+const SHELL_KEYWORDS: &[&str] = &["if", "then", "else", "elif", "fi", "for", "while", "do", "done", "case", "esac", "function", "return", "exit", "local", "export"];
+const YAML_JSON_KEYWORDS: &[&str] = &["true", "false", "null"];
+
+fn rules_for_extension(extension: &str) -> Option<LanguageRules> {
+    match extension.to_ascii_lowercase().as_str() {
+        "rs" => Some(LanguageRules { name: "rust", line_comment: Some("//"), block_comment: Some(("/*", "*/")), string_delims: &['"'], keywords: RUST_KEYWORDS }),
+        "py" => Some(LanguageRules { name: "python", line_comment: Some("#"), block_comment: None, string_delims: &['"', '\''], keywords: PYTHON_KEYWORDS }),
+        "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" => Some(LanguageRules { name: "javascript", line_comment: Some("//"), block_comment: Some(("/*", "*/")), string_delims: &['"', '\'', '`'], keywords: JS_KEYWORDS }),
+        "go" => Some(LanguageRules { name: "go", line_comment: Some("//"), block_comment: Some(("/*", "*/")), string_delims: &['"', '`'], keywords: GO_KEYWORDS }),
+        "c" | "h" | "cpp" | "hpp" | "cc" | "cxx" | "java" => Some(LanguageRules { name: "c-like", line_comment: Some("//"), block_comment: Some(("/*", "*/")), string_delims: &['"', '\''], keywords: C_LIKE_KEYWORDS }),
+        "sh" | "bash" | "zsh" => Some(LanguageRules { name: "shell", line_comment: Some("#"), block_comment: None, string_delims: &['"', '\''], keywords: SHELL_KEYWORDS }),
+        "yml" | "yaml" => Some(LanguageRules { name: "yaml", line_comment: Some("#"), block_comment: None, string_delims: &['"', '\''], keywords: YAML_JSON_KEYWORDS }),
+        "json" => Some(LanguageRules { name: "json", line_comment: None, block_comment: None, string_delims: &['"'], keywords: YAML_JSON_KEYWORDS }),
+        "toml" | "ini" | "cfg" | "conf" => Some(LanguageRules { name: "config", line_comment: Some("#"), block_comment: None, string_delims: &['"', '\''], keywords: &[] }),
+        "log" => Some(LanguageRules { name: "log", line_comment: None, block_comment: None, string_delims: &[], keywords: &[] }),
+        _ => None,
+    }
+}
+
+/// Picks which document-view component should handle `document_identifier`:
+/// PDF-to-HTML for PDFs, the syntax-highlighted code viewer for a known
+/// source/config extension, and the plain extracted-text viewer otherwise.
+pub async fn get_document_type(document_identifier: DocumentIdentifier) -> anyhow::Result<DocumentType> {
+    if get_document_type_is_pdf(document_identifier.clone()).await? {
+        return Ok(DocumentType::Pdf);
+    }
+
+    let path = get_file_path(document_identifier).await?;
+    match extension_of(&path).and_then(rules_for_extension) {
+        Some(_) => Ok(DocumentType::Code),
+        None => Ok(DocumentType::Text),
+    }
+}
+
+pub async fn get_code_highlight(document_identifier: DocumentIdentifier) -> anyhow::Result<CodeHighlightResponse> {
+    let path = get_file_path(document_identifier.clone()).await?;
+    let rules = extension_of(&path).and_then(rules_for_extension);
+    let language = rules.as_ref().map(|r| r.name).unwrap_or("plaintext").to_string();
+
+    let (_size, _range, mut stream) = get_document_content_stream(document_identifier, None).await?;
+    let mut buffer = Vec::new();
+    'read: while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        for byte in chunk {
+            if buffer.len() >= MAX_CODE_HIGHLIGHT_BYTES {
+                break 'read;
+            }
+            buffer.push(byte);
+        }
+    }
+    let text = String::from_utf8_lossy(&buffer).into_owned();
+
+    let tokens = tokenize(&text, rules.as_ref());
+    let lines = split_into_lines(&text, tokens);
+
+    Ok(CodeHighlightResponse { language, lines })
+}
+
+fn extension_of(path: &str) -> Option<String> {
+    let filename = path.rsplit('/').next().unwrap_or(path);
+    filename.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase())
+}
+
+/// Classifies `text` into non-overlapping, ordered `(start, end, class)`
+/// char-offset ranges, same shape as the frontend's `tokenize_ranges` but
+/// covering the whole file rather than a single highlighted-query fragment.
+fn tokenize(text: &str, rules: Option<&LanguageRules>) -> Vec<(usize, usize, CodeTokenClass)> {
+    let Some(rules) = rules else {
+        let len = text.chars().count();
+        return if len == 0 { vec![] } else { vec![(0, len, CodeTokenClass::Plain)] };
+    };
+    let keywords: HashSet<&str> = rules.keywords.iter().copied().collect();
+    let chars: Vec<char> = text.chars().collect();
+    let len = chars.len();
+    let mut ranges = Vec::new();
+    let mut pos = 0usize;
+
+    let starts_with = |pos: usize, needle: &str| -> bool {
+        let needle_chars: Vec<char> = needle.chars().collect();
+        pos + needle_chars.len() <= len && chars[pos..pos + needle_chars.len()] == needle_chars[..]
+    };
+
+    while pos < len {
+        let start = pos;
+
+        if let Some(line_comment) = rules.line_comment {
+            if starts_with(pos, line_comment) {
+                while pos < len && chars[pos] != '\n' {
+                    pos += 1;
+                }
+                ranges.push((start, pos, CodeTokenClass::Comment));
+                continue;
+            }
+        }
+
+        if let Some((open, close)) = rules.block_comment {
+            if starts_with(pos, open) {
+                pos += open.chars().count();
+                while pos < len && !starts_with(pos, close) {
+                    pos += 1;
+                }
+                pos = (pos + close.chars().count()).min(len);
+                ranges.push((start, pos, CodeTokenClass::Comment));
+                continue;
+            }
+        }
+
+        if rules.string_delims.contains(&chars[pos]) {
+            let delim = chars[pos];
+            pos += 1;
+            while pos < len && chars[pos] != delim {
+                if chars[pos] == '\\' && pos + 1 < len {
+                    pos += 2;
+                } else {
+                    pos += 1;
+                }
+            }
+            pos = (pos + 1).min(len);
+            ranges.push((start, pos, CodeTokenClass::String));
+            continue;
+        }
+
+        if chars[pos].is_ascii_digit() {
+            while pos < len && (chars[pos].is_ascii_alphanumeric() || chars[pos] == '.' || chars[pos] == '_') {
+                pos += 1;
+            }
+            ranges.push((start, pos, CodeTokenClass::Number));
+            continue;
+        }
+
+        if chars[pos].is_alphabetic() || chars[pos] == '_' {
+            while pos < len && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            let word: String = chars[start..pos].iter().collect();
+            let class = if keywords.contains(word.as_str()) { CodeTokenClass::Keyword } else { CodeTokenClass::Plain };
+            ranges.push((start, pos, class));
+            continue;
+        }
+
+        pos += 1;
+        ranges.push((start, pos, CodeTokenClass::Plain));
+    }
+
+    ranges
+}
+
+/// Splits `text` by `\n` and carves the flat token-range list from
+/// `tokenize` at each line boundary, merging adjacent same-line fragments of
+/// the same class so punctuation doesn't explode into one token per character.
+fn split_into_lines(text: &str, tokens: Vec<(usize, usize, CodeTokenClass)>) -> Vec<CodeHighlightLine> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut lines = Vec::new();
+    let mut line_number = 1u32;
+    let mut line_start = 0usize;
+
+    let mut push_line = |start: usize, end: usize, line_number: u32| {
+        let mut line_tokens: Vec<CodeToken> = Vec::new();
+        for &(token_start, token_end, class) in &tokens {
+            let token_start = token_start.max(start);
+            let token_end = token_end.min(end);
+            if token_start >= token_end {
+                continue;
+            }
+            let text: String = chars[token_start..token_end].iter().collect();
+            if let Some(last) = line_tokens.last_mut() {
+                if last.class == class {
+                    last.text.push_str(&text);
+                    continue;
+                }
+            }
+            line_tokens.push(CodeToken { text, class });
+        }
+        lines.push(CodeHighlightLine { line_number, tokens: line_tokens });
+    };
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '\n' {
+            push_line(line_start, i, line_number);
+            line_number += 1;
+            line_start = i + 1;
+        }
+    }
+    if line_start < chars.len() || chars.is_empty() {
+        push_line(line_start, chars.len(), line_number);
+    }
+
+    lines
+}
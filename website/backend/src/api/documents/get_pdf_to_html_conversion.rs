@@ -1,10 +1,10 @@
 use anyhow::Context;
 use clickhouse::Row;
-use common::{document_metadata::DocumentMetadataTableInfo, pdf_to_html_conversion::PDFToHtmlConversionResponse, search_result::DocumentIdentifier};
+use common::{document_metadata::DocumentMetadataTableInfo, pdf_to_html_conversion::{PDFPageRangeResponse, PDFToHtmlConversionResponse}, search_result::DocumentIdentifier};
 use reqwest::Body;
 use serde::{Deserialize, Serialize};
 
-use crate::api::documents::{download_document::get_document_content_stream, get_raw_metadata::get_raw_metadata};
+use crate::api::documents::{download_document::get_document_content_stream, get_raw_metadata::get_raw_metadata, math_placeholders::inject_math_placeholders};
 use crate::db_utils::clickhouse_utils::get_clickhouse_client;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
@@ -16,6 +16,7 @@ struct PDFToHtmlCacheRow {
     pub pages: Vec<String>,
     pub page_width_px: f32,
     pub page_height_px: f32,
+    pub has_math: bool,
 }
 
 pub async fn get_document_type_is_pdf(document_identifier: DocumentIdentifier) -> anyhow::Result<bool> {
@@ -25,7 +26,7 @@ pub async fn get_document_type_is_pdf(document_identifier: DocumentIdentifier) -
 
 pub async fn get_pdf_to_html_conversion(document_identifier: DocumentIdentifier) -> anyhow::Result<PDFToHtmlConversionResponse> {
     let client = get_clickhouse_client();
-    let query = "SELECT collection_dataset, pdf_hash, page_count, styles, pages, page_width_px, page_height_px FROM pdf_to_html_cache WHERE collection_dataset = ? AND pdf_hash = ? LIMIT 1";
+    let query = "SELECT collection_dataset, pdf_hash, page_count, styles, pages, page_width_px, page_height_px, has_math FROM pdf_to_html_cache WHERE collection_dataset = ? AND pdf_hash = ? LIMIT 1";
     let query = client.query(query)
         .bind(&document_identifier.collection_dataset)
         .bind(&document_identifier.file_hash);
@@ -34,10 +35,12 @@ pub async fn get_pdf_to_html_conversion(document_identifier: DocumentIdentifier)
     if let Some(row) = result.into_iter().next() {
         tracing::info!("PDF to HTML cache: HIT");
         return Ok(PDFToHtmlConversionResponse {
+            page_count: row.page_count,
             pages: row.pages,
             styles: row.styles,
             page_width_px: row.page_width_px,
             page_height_px: row.page_height_px,
+            has_math: row.has_math,
         });
     }
 
@@ -52,6 +55,7 @@ pub async fn get_pdf_to_html_conversion(document_identifier: DocumentIdentifier)
         pages: response.pages.clone(),
         page_width_px: response.page_width_px,
         page_height_px: response.page_height_px,
+        has_math: response.has_math,
     };
 
     tracing::info!("PDF to HTML cache: SET");
@@ -63,6 +67,26 @@ pub async fn get_pdf_to_html_conversion(document_identifier: DocumentIdentifier)
 }
 
 
+/// Slices a window of `window_size` pages starting at `start_page` out of
+/// the (cached) full conversion, so `DocumentPreviewForPdf`'s
+/// continuous-scroll viewer only has to ship the pages near the viewport
+/// instead of the whole document on every navigation.
+pub async fn get_pdf_to_html_page_range(document_identifier: DocumentIdentifier, start_page: u32, window_size: u32) -> anyhow::Result<PDFPageRangeResponse> {
+    let full = get_pdf_to_html_conversion(document_identifier).await?;
+    let start = (start_page as usize).min(full.pages.len());
+    let end = (start + window_size as usize).min(full.pages.len());
+
+    Ok(PDFPageRangeResponse {
+        start_page: start as u32,
+        pages: full.pages[start..end].to_vec(),
+        styles: full.styles,
+        page_width_px: full.page_width_px,
+        page_height_px: full.page_height_px,
+        has_math: full.has_math,
+        page_count: full.page_count,
+    })
+}
+
 async fn make_pdf_to_html_conversion(document_identifier: DocumentIdentifier) -> anyhow::Result<PDFToHtmlConversionResponse> {
     let is_pdf = get_document_type_is_pdf(document_identifier.clone()).await?;
     if !is_pdf {
@@ -70,7 +94,7 @@ async fn make_pdf_to_html_conversion(document_identifier: DocumentIdentifier) ->
     }
     tracing::info!("Document is a PDF, converting to HTML");
 
-    let (stream_size, doc_stream) = get_document_content_stream(document_identifier.clone()).await?;
+    let (stream_size, _range, doc_stream) = get_document_content_stream(document_identifier.clone(), None).await?;
     tracing::info!("Document stream received");
     let client = reqwest::Client::new();
     let response = client.post(format!("{}", std::env::var("PDF_TO_HTML_ENDPOINT").context("PDF_TO_HTML_ENDPOINT is not set")?))
@@ -81,7 +105,17 @@ async fn make_pdf_to_html_conversion(document_identifier: DocumentIdentifier) ->
     tracing::info!("Response received");
     let response = response.error_for_status()?;
     let body = response.text().await?;
-    let body = serde_json::from_str::<PDFToHtmlConversionResponse>(&body)?;
+    let mut body = serde_json::from_str::<PDFToHtmlConversionResponse>(&body)?;
+
+    let mut has_math = false;
+    for page in body.pages.iter_mut() {
+        let (page_with_placeholders, page_has_math) = inject_math_placeholders(page);
+        *page = page_with_placeholders;
+        has_math |= page_has_math;
+    }
+    body.has_math = has_math;
+    body.page_count = body.pages.len() as u32;
+
     Ok(body)
 }
 
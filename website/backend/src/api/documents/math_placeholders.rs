@@ -0,0 +1,129 @@
+//! Detects LaTeX-style math delimiters (`$...$` and `$$...$$`) in converted
+//! PDF page HTML and swaps each one for a placeholder `<span>` the
+//! client-side KaTeX pass (injected into the viewer's `iframe` `srcdoc`)
+//! typesets by class name, so math renders without a server round trip per
+//! document.
+
+/// Wraps each detected math span in a `<span class="hoover4-math" ...>`
+/// placeholder carrying the raw TeX source in a `data-tex` attribute, and
+/// reports whether any math was found so callers can skip the KaTeX script
+/// entirely for documents with no math.
+///
+/// Delimiter matching is balanced-aware: a `$` with no matching closing
+/// delimiter before the end of the page (or before a `<` tag boundary, so an
+/// unmatched `$` can't swallow unrelated markup) is left as literal text.
+/// `\$` is treated as an escaped, literal dollar sign. Spans already inside
+/// a `<code>` or `<pre>` element are left untouched, since they're already
+/// displayed verbatim.
+pub fn inject_math_placeholders(html: &str) -> (String, bool) {
+    let chars: Vec<char> = html.chars().collect();
+    let mut out = String::with_capacity(html.len());
+    let mut i = 0;
+    let mut code_depth: u32 = 0;
+    let mut found_any = false;
+
+    while i < chars.len() {
+        if let Some(len) = match_tag(&chars, i, "pre", false).or_else(|| match_tag(&chars, i, "code", false)) {
+            code_depth += 1;
+            out.extend(&chars[i..i + len]);
+            i += len;
+            continue;
+        }
+        if let Some(len) = match_tag(&chars, i, "pre", true).or_else(|| match_tag(&chars, i, "code", true)) {
+            code_depth = code_depth.saturating_sub(1);
+            out.extend(&chars[i..i + len]);
+            i += len;
+            continue;
+        }
+
+        if code_depth == 0 && chars[i] == '\\' && chars.get(i + 1) == Some(&'$') {
+            out.push('$');
+            i += 2;
+            continue;
+        }
+
+        if code_depth == 0 && chars[i] == '$' {
+            let is_block = chars.get(i + 1) == Some(&'$');
+            let delimiter_len = if is_block { 2 } else { 1 };
+            let search_from = i + delimiter_len;
+            if let Some(end) = find_closing_delimiter(&chars, search_from, delimiter_len) {
+                let tex_source: String = chars[search_from..end].iter().collect();
+                out.push_str(&render_math_placeholder(&tex_source, is_block));
+                i = end + delimiter_len;
+                found_any = true;
+                continue;
+            }
+        }
+
+        out.push(chars[i]);
+        i += 1;
+    }
+
+    (out, found_any)
+}
+
+fn render_math_placeholder(tex_source: &str, is_block: bool) -> String {
+    format!(
+        "<span class=\"hoover4-math\" data-display=\"{}\" data-tex=\"{}\">{}</span>",
+        is_block,
+        escape_html_attr(tex_source),
+        escape_html_text(tex_source),
+    )
+}
+
+/// Scans forward from `from` for the delimiter (`$` or `$$`), skipping
+/// escaped `\$`. Bails out (returning `None`, leaving the opening delimiter
+/// as literal text) on a tag boundary or end of input, since a real closing
+/// delimiter should never need to cross into markup.
+fn find_closing_delimiter(chars: &[char], from: usize, delimiter_len: usize) -> Option<usize> {
+    let mut i = from;
+    while i + delimiter_len <= chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '<' => return None,
+            '$' if chars[i..i + delimiter_len].iter().all(|c| *c == '$') && (delimiter_len == 1 || chars.get(i + 1) == Some(&'$')) => {
+                return Some(i);
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Matches an HTML open tag (`closing: false`, e.g. `<pre ...>`) or close
+/// tag (`closing: true`, e.g. `</pre>`) for `name` starting at `i`, returning
+/// how many chars it spans so the caller can copy it through untouched.
+fn match_tag(chars: &[char], i: usize, name: &str, closing: bool) -> Option<usize> {
+    if chars.get(i) != Some(&'<') {
+        return None;
+    }
+    let mut j = i + 1;
+    if closing {
+        if chars.get(j) != Some(&'/') {
+            return None;
+        }
+        j += 1;
+    }
+    for c in name.chars() {
+        if !chars.get(j).is_some_and(|ch| ch.eq_ignore_ascii_case(&c)) {
+            return None;
+        }
+        j += 1;
+    }
+    match chars.get(j) {
+        Some(' ') | Some('\t') | Some('\n') | Some('>') | Some('/') => {}
+        _ => return None,
+    }
+    while chars.get(j).is_some() && chars[j] != '>' {
+        j += 1;
+    }
+    if chars.get(j) == Some(&'>') { Some(j + 1 - i) } else { None }
+}
+
+fn escape_html_text(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn escape_html_attr(s: &str) -> String {
+    escape_html_text(s).replace('"', "&quot;")
+}
@@ -6,4 +6,6 @@ pub mod get_text_sources;
 pub mod get_pdf_to_html_conversion;
 pub mod search_document_text;
 pub mod download_document;
-pub mod search_document_pdf_html_preview;
\ No newline at end of file
+pub mod search_document_pdf_html_preview;
+pub mod math_placeholders;
+pub mod get_code_highlight;
\ No newline at end of file
@@ -6,6 +6,8 @@ use html5ever::tokenizer::{Token, TokenSink, TokenSinkResult, Tokenizer, Tokeniz
 use common::search_result::DocumentIdentifier;
 
 use crate::api::documents::get_pdf_to_html_conversion::get_pdf_to_html_conversion;
+use crate::db_utils::token_match::DEFAULT_PROXIMITY_SLACK;
+use crate::db_utils::query_parser::{parse_query, count_parsed_query_hits};
 
 pub async fn search_html_preview_hit_counts(document_identifier: DocumentIdentifier ,query: String) -> anyhow::Result<BTreeMap<u32, u32>> {
     let doc = get_pdf_to_html_conversion(document_identifier).await?;
@@ -72,9 +74,8 @@ fn _count_html_page_hits(page: &str, query: &str) -> anyhow::Result<u32> {
     let _ = tokenizer.feed(&input);
     tokenizer.end();
 
-    let query = query.to_lowercase();
-    let text_content = tokenizer.sink.text.borrow().to_lowercase();
-
-    let count = text_content.matches(&query).count() as u32;
+    let text_content = tokenizer.sink.text.borrow();
+    let parsed_query = parse_query(query);
+    let count = count_parsed_query_hits(&text_content, &parsed_query, DEFAULT_PROXIMITY_SLACK);
     Ok(count)
 }
@@ -3,7 +3,9 @@
 use common::{document_text_sources::{DocumentTextSourceHit, DocumentTextSourceHitCount}, search_result::DocumentIdentifier};
 use serde::{Deserialize, Serialize};
 
-use crate::db_utils::{decompose_spans::decompose_text_into_spans, manticore_utils::manticore_search_sql};
+use crate::db_utils::{decompose_spans::{decompose_text_into_spans, crop_spans_around_hits}, manticore_utils::manticore_search_sql};
+use crate::db_utils::token_match::{DEFAULT_PROXIMITY_SLACK, tokenize, normalize_text};
+use crate::db_utils::query_parser::{parse_query, count_parsed_query_hits, QueryClause};
 use crate::api::search::search_sql::SQL_OPTIONS_CLAUSE;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -13,26 +15,56 @@ struct DocumentHits {
     text: String,
 }
 
+/// Manticore `HIGHLIGHT()` options used when the caller wants the whole
+/// page's text back (the default): no contextual window, so
+/// `crop_spans_around_hits` does the windowing client-side instead.
+const FULL_PAGE_HIGHLIGHT_OPTIONS: &str = "
+                    limit=0,
+                    force_all_words=1,
+                    html_strip_mode=retain,
+                    around=0,
+                    before_match='<hoover4_strong>',
+                    after_match='</hoover4_strong>',
+                    force_snippets=1
+";
+
+/// Builds the `HIGHLIGHT()` option list for [`search_document_text_for_hits`].
+/// `around == 0` keeps the existing whole-page fetch (`FULL_PAGE_HIGHLIGHT_OPTIONS`);
+/// `around > 0` instead asks Manticore for real contextual snippets, `around`
+/// words to each side of a match, up to `snippet_limit` words per fragment,
+/// matching the `attributes_to_crop`/`crop_length` concept `highlight_field_sql`
+/// already applies to result-list snippets.
+fn document_text_highlight_options(around: u32, snippet_limit: u32) -> String {
+    if around == 0 {
+        return FULL_PAGE_HIGHLIGHT_OPTIONS.to_string();
+    }
+    let limit_chars = snippet_limit.saturating_mul(10).min(100_000);
+    format!("
+                    limit={limit_chars},
+                    limit_words={snippet_limit},
+                    html_strip_mode=retain,
+                    around={around},
+                    before_match='<hoover4_strong>',
+                    after_match='</hoover4_strong>'
+    ")
+}
+
 pub async fn search_document_text_for_hits(
     document_identifier: DocumentIdentifier,
     find_query: String,
     extracted_by: String,
     page_id: u32,
+    crop_radius: u32,
+    around: u32,
+    snippet_limit: u32,
 ) -> anyhow::Result<Vec<DocumentTextSourceHit>>
 {
+    let highlight_options = document_text_highlight_options(around, snippet_limit);
     let sql = format!(r#"
             SELECT
                 extracted_by,
                 page_id,
-                highlight({{
-                    limit=0,
-                    force_all_words=1,
-                    html_strip_mode=retain,
-                    around=0,
-                    before_match='<hoover4_strong>',
-                    after_match='</hoover4_strong>',
-                    force_snippets=1
-                }}) as text
+                highlight({{{highlight_options}}}) as text
             FROM doc_text_pages
             WHERE file_hash = {} AND collection_dataset = {} AND extracted_by = {} AND page_id = {}
             AND MATCH({})
@@ -50,7 +82,7 @@ pub async fn search_document_text_for_hits(
     let result = hits.into_iter().map(|hit| DocumentTextSourceHit {
         extracted_by: hit._source.extracted_by,
         page_id: hit._source.page_id,
-        highlight_text_spans: decompose_text_into_spans(hit._source.text),
+        highlight_text_spans: crop_spans_around_hits(decompose_text_into_spans(hit._source.text, &find_query, Some(hit._source.page_id)), crop_radius as usize),
     }).collect::<Vec<_>>();
 
     Ok(result)
@@ -86,21 +118,45 @@ pub async fn search_document_text_for_hit_count(
     );
     let response = manticore_search_sql::<DocumentHits>(sql).await?;
     let hits = response.hits.hits;
-    let result = hits.into_iter().map(|hit| DocumentTextSourceHit {
-        extracted_by: hit._source.extracted_by,
-        page_id: hit._source.page_id,
-        highlight_text_spans: decompose_text_into_spans(hit._source.text),
-    }).collect::<Vec<_>>();
 
-    let result = result.into_iter().map(|hits| {
-        let hit_count = hits.highlight_text_spans.iter().filter(|h| h.is_highlighted).count();
+    // Count hits with the shared typo-tolerant, word-boundary-aware matcher
+    // (rather than the raw span count) so results stay consistent with the
+    // HTML preview hit counting, and route through the same phrase/exclusion
+    // parsing used there.
+    let parsed_query = parse_query(&find_query);
+    let has_phrase_clause = parsed_query.clauses.iter().any(|c| matches!(c, QueryClause::Phrase(_)));
 
-        DocumentTextSourceHitCount {
-            extracted_by: hits.extracted_by,
-            page_id: hits.page_id,
+    let mut scored = hits.into_iter().map(|hit| {
+        let plain_text = strip_highlight_tags(&hit._source.text);
+        let hit_count = count_parsed_query_hits(&plain_text, &parsed_query, DEFAULT_PROXIMITY_SLACK);
+        let score = rank_text_source_hit(&plain_text, has_phrase_clause, hit_count, &hit._source.extracted_by);
+
+        (score, DocumentTextSourceHitCount {
+            extracted_by: hit._source.extracted_by,
+            page_id: hit._source.page_id,
             hit_count: hit_count as u64,
-        }
+        })
     }).collect::<Vec<_>>();
+
+    // Rank by combined score (hit density x extractor quality x phrase
+    // specificity) rather than raw hit_count, so a noisy OCR page full of
+    // scattered single-token hits doesn't outrank a clean embedded-text page
+    // with fewer but more specific matches.
+    scored.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let result = scored.into_iter().map(|(_score, item)| item).collect::<Vec<_>>();
     Ok(result)
 }
 
+/// Combines hit density, extractor quality, and full-phrase specificity
+/// into a single score used to rank candidate text sources.
+fn rank_text_source_hit(plain_text: &str, has_phrase_clause: bool, hit_count: u32, extracted_by: &str) -> f64 {
+    let token_count = tokenize(&normalize_text(plain_text)).len().max(1);
+    let hit_density = hit_count as f64 / token_count as f64;
+    let quality_weight = common::search_const::extractor_quality_weight(extracted_by);
+    let phrase_bonus = if has_phrase_clause && hit_count > 0 { 1.5 } else { 1.0 };
+    hit_density * quality_weight * phrase_bonus
+}
+
+fn strip_highlight_tags(text: &str) -> String {
+    text.replace("<hoover4_strong>", "").replace("</hoover4_strong>", "")
+}
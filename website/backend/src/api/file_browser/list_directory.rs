@@ -0,0 +1,81 @@
+//! Directory-listing endpoint for the collection/folder tree explorer.
+
+use std::collections::BTreeMap;
+
+use clickhouse::Row;
+use common::file_browser::FileBrowserEntry;
+use serde::Deserialize;
+
+use crate::db_utils::clickhouse_utils::get_clickhouse_client;
+
+#[derive(Debug, Clone, Deserialize, Row)]
+struct VfsFileRow {
+    path: String,
+    hash: String,
+    size_bytes: Option<u64>,
+}
+
+/// Escapes ClickHouse's `LIKE` wildcard characters (`%`, `_`) and the
+/// backslash that introduces the escape itself, so a `path_prefix` coming
+/// from an investigated, adversary-controlled document path can't be
+/// misinterpreted as a wildcard and over-match unrelated paths.
+fn escape_like_pattern(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Lists the immediate children of `path_prefix` within `collection_dataset`,
+/// derived by splitting `vfs_files.path` on `/`. `path_prefix` is the empty
+/// string for the collection root. Directories are deduplicated in-process
+/// since there's no next-path-segment aggregate to push this down to
+/// ClickHouse with.
+pub async fn list_directory_entries(collection_dataset: String, path_prefix: String) -> anyhow::Result<Vec<FileBrowserEntry>> {
+    let client = get_clickhouse_client();
+    let prefix = if path_prefix.is_empty() { String::new() } else { format!("{path_prefix}/") };
+    let like_pattern = format!("{}%", escape_like_pattern(&prefix));
+
+    let query = "
+        SELECT vfs_files.path as path, vfs_files.hash as hash, blobs.blob_size_bytes as size_bytes
+        FROM vfs_files
+        LEFT JOIN blobs
+        ON vfs_files.collection_dataset = blobs.collection_dataset
+        AND vfs_files.hash = blobs.blob_hash
+        WHERE vfs_files.collection_dataset = ?
+        AND vfs_files.path LIKE ?
+    ";
+    let rows = client.query(query)
+        .bind(&collection_dataset)
+        .bind(&like_pattern)
+        .fetch_all::<VfsFileRow>().await?;
+
+    let mut directories: BTreeMap<String, ()> = BTreeMap::new();
+    let mut files: BTreeMap<String, FileBrowserEntry> = BTreeMap::new();
+
+    for row in rows {
+        let Some(remainder) = row.path.strip_prefix(&prefix) else { continue };
+        if remainder.is_empty() {
+            continue;
+        }
+        match remainder.split_once('/') {
+            Some((dir_name, _rest)) => {
+                directories.insert(dir_name.to_string(), ());
+            }
+            None => {
+                files.insert(remainder.to_string(), FileBrowserEntry {
+                    name: remainder.to_string(),
+                    path: row.path.clone(),
+                    is_directory: false,
+                    file_hash: Some(row.hash),
+                    size_bytes: row.size_bytes,
+                });
+            }
+        }
+    }
+
+    let mut entries: Vec<FileBrowserEntry> = directories.into_keys().map(|name| {
+        let path = format!("{prefix}{name}");
+        FileBrowserEntry { name, path, is_directory: true, file_hash: None, size_bytes: None }
+    }).collect();
+    entries.extend(files.into_values());
+
+    Ok(entries)
+}
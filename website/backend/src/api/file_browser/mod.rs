@@ -0,0 +1,6 @@
+//! File browser API route handlers and module exports.
+
+mod list_directory;
+pub use list_directory::list_directory_entries;
+
+pub use crate::api::list_datasets::list_dataset_ids as list_collections;
@@ -0,0 +1,66 @@
+//! Server-side persistence for named, re-runnable saved searches.
+
+use clickhouse::Row;
+use common::saved_search::SavedSearch;
+use serde::{Deserialize, Serialize};
+
+use crate::db_utils::clickhouse_utils::get_clickhouse_client;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Row)]
+struct SavedSearchRow {
+    id: String,
+    name: String,
+    encoded_query: String,
+    date_created: String,
+    last_hit_count: Option<u64>,
+}
+
+impl From<SavedSearchRow> for SavedSearch {
+    fn from(row: SavedSearchRow) -> Self {
+        SavedSearch { id: row.id, name: row.name, encoded_query: row.encoded_query, date_created: row.date_created, last_hit_count: row.last_hit_count }
+    }
+}
+
+const SAVED_SEARCH_COLUMNS: &str = "id, name, encoded_query, toString(date_created) as date_created, last_hit_count";
+
+/// Persists `name`/`encoded_query` as a new saved search, stamping
+/// `last_hit_count` as the hit count observed at save time.
+pub async fn save_search(name: String, encoded_query: String, last_hit_count: Option<u64>) -> anyhow::Result<SavedSearch> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let client = get_clickhouse_client();
+    let insert_sql = "
+    INSERT INTO saved_searches (id, name, encoded_query, last_hit_count)
+    VALUES (?, ?, ?, ?)
+    ";
+    client
+        .query(insert_sql)
+        .bind(&id)
+        .bind(&name)
+        .bind(&encoded_query)
+        .bind(last_hit_count)
+        .execute()
+        .await?;
+
+    let select_sql = format!("SELECT {SAVED_SEARCH_COLUMNS} FROM saved_searches WHERE id = ? LIMIT 1");
+    let rows = client.query(&select_sql).bind(&id).fetch_all::<SavedSearchRow>().await?;
+    rows.into_iter()
+        .next()
+        .map(SavedSearch::from)
+        .ok_or_else(|| anyhow::anyhow!("save_search: row not found immediately after insert"))
+}
+
+/// Lists every saved search, most recently created first.
+pub async fn list_saved_searches() -> anyhow::Result<Vec<SavedSearch>> {
+    let client = get_clickhouse_client();
+    let sql = format!("SELECT {SAVED_SEARCH_COLUMNS} FROM saved_searches ORDER BY date_created DESC");
+    let rows = client.query(&sql).fetch_all::<SavedSearchRow>().await?;
+    Ok(rows.into_iter().map(SavedSearch::from).collect())
+}
+
+/// Drops a saved search by id.
+pub async fn delete_saved_search(id: String) -> anyhow::Result<()> {
+    let client = get_clickhouse_client();
+    let sql = "ALTER TABLE saved_searches DELETE WHERE id = ?";
+    client.query(sql).bind(id).execute().await?;
+    Ok(())
+}
@@ -1,7 +1,7 @@
 //! Search API route handlers and module exports.
 
 mod search_for_results;
-pub use search_for_results::search_for_results;
+pub use search_for_results::{search_for_results, stream_search_snippets};
 
 mod search_for_results_hit_count;
 pub use search_for_results_hit_count::search_for_results_hit_count;
@@ -10,4 +10,18 @@ pub use search_for_results_hit_count::search_for_results_hit_count;
 mod search_facets;
 pub use search_facets::search_string_facet;
 
+mod search_unified;
+pub use search_unified::search_unified;
+
+mod search_similar;
+pub use search_similar::search_similar;
+
+mod search_embed;
+
+mod search_suggestions;
+pub use search_suggestions::search_suggestions;
+
+mod search_export;
+pub use search_export::export_search_results;
+
 pub mod search_sql;
\ No newline at end of file
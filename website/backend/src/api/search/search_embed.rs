@@ -0,0 +1,26 @@
+//! Embeds query text for hybrid/semantic search by calling out to an
+//! external embedding service, the same "POST to an endpoint from an env
+//! var" shape as `get_pdf_to_html_conversion`'s `PDF_TO_HTML_ENDPOINT` call.
+
+use anyhow::Context;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct EmbedQueryResponse {
+    embedding: Vec<f32>,
+}
+
+/// Embeds `query_string` via `EMBEDDING_ENDPOINT`, for the vector side of
+/// hybrid search's Reciprocal Rank Fusion.
+pub async fn embed_query_text(query_string: &str) -> anyhow::Result<Vec<f32>> {
+    let endpoint = std::env::var("EMBEDDING_ENDPOINT").context("EMBEDDING_ENDPOINT is not set")?;
+    let client = reqwest::Client::new();
+    let response = client
+        .post(endpoint)
+        .json(&serde_json::json!({ "text": query_string }))
+        .send()
+        .await?;
+    let response = response.error_for_status()?;
+    let body: EmbedQueryResponse = response.json().await?;
+    Ok(body.embedding)
+}
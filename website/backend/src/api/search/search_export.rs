@@ -0,0 +1,128 @@
+//! Bulk export of an entire search result set, beyond the one-page cap
+//! [`crate::api::search::search_for_results`] enforces, for pulling a whole
+//! filtered corpus slice out for offline analysis.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use common::{search_const::MAX_PAGINATION_DOCUMENT_LIMIT, search_export::ExportFormat, search_query::SearchQuery, search_result::SearchResultDocumentItem};
+use futures::{Stream, StreamExt};
+
+use crate::api::documents::download_document::{get_blob_filename, get_document_content_stream};
+use crate::api::search::search_for_results::search_for_results;
+
+/// Size of each base64-encoded text chunk emitted for an
+/// [`ExportFormat::Zip`] export, so a caller streaming the response can show
+/// download progress instead of waiting on one giant line.
+const ZIP_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Walks every page of `query` via `search_for_results`'s own `next_hash`
+/// cursor, starting at page 0, and stops once a page reports no further
+/// `next_hash` or [`MAX_PAGINATION_DOCUMENT_LIMIT`] documents have been
+/// collected, whichever comes first.
+fn walk_all_results(query: SearchQuery) -> impl Stream<Item = anyhow::Result<SearchResultDocumentItem>> {
+    struct State {
+        query: SearchQuery,
+        page: u64,
+        fetched: u64,
+        done: bool,
+    }
+
+    futures::stream::unfold(State { query, page: 0, fetched: 0, done: false }, |mut state| async move {
+        if state.done {
+            return None;
+        }
+        let page_result = match search_for_results(state.query.clone(), state.page).await {
+            Ok(page_result) => page_result,
+            Err(e) => {
+                state.done = true;
+                return Some((vec![Err(e)], state));
+            }
+        };
+        state.fetched += page_result.results.len() as u64;
+        state.page += 1;
+        state.done = page_result.next_hash.is_none() || state.fetched >= MAX_PAGINATION_DOCUMENT_LIMIT;
+        let items = page_result.results.into_iter().map(Ok).collect::<Vec<_>>();
+        Some((items, state))
+    })
+    .flat_map(futures::stream::iter)
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row(item: &SearchResultDocumentItem) -> String {
+    format!(
+        "{},{},{}\n",
+        csv_escape(&item.collection_dataset),
+        csv_escape(&item.file_hash),
+        csv_escape(&item.title),
+    )
+}
+
+/// Builds an in-memory zip archive out of every matched document's original
+/// file (fetched the same way [`crate::server_extra::download_document`]
+/// serves single-document downloads), then re-emits it as a sequence of
+/// base64-encoded text chunks so it fits through the same text-streaming
+/// codec used for CSV/NDJSON.
+async fn build_zip_chunks(query: SearchQuery) -> anyhow::Result<Vec<anyhow::Result<String>>> {
+    let mut zip_buffer = std::io::Cursor::new(Vec::new());
+    let mut zip = zip::ZipWriter::new(&mut zip_buffer);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+    let mut seen_names = std::collections::BTreeSet::new();
+    let mut items = walk_all_results(query);
+    while let Some(item) = items.next().await {
+        let item = item?;
+        let document_identifier = item.document_identifier();
+        let filename = get_blob_filename(document_identifier.clone()).await.unwrap_or_else(|_| item.file_hash.clone());
+        let mut unique_name = filename.clone();
+        let mut suffix = 1;
+        while !seen_names.insert(unique_name.clone()) {
+            suffix += 1;
+            unique_name = format!("{filename} ({suffix})");
+        }
+
+        let (_size, _range, mut content) = get_document_content_stream(document_identifier, None).await?;
+        zip.start_file(unique_name, options)?;
+        while let Some(chunk) = content.next().await {
+            std::io::Write::write_all(&mut zip, &chunk?)?;
+        }
+    }
+    zip.finish()?;
+
+    let bytes = zip_buffer.into_inner();
+    let chunks = bytes
+        .chunks(ZIP_CHUNK_BYTES)
+        .map(|chunk| anyhow::Ok(format!("{}\n", STANDARD.encode(chunk))))
+        .collect::<Vec<_>>();
+    Ok(chunks)
+}
+
+/// Re-runs `query` past the single-page cap enforced by
+/// [`crate::api::search::search_for_results`], walking its `next_hash`
+/// cursor up to [`MAX_PAGINATION_DOCUMENT_LIMIT`] documents, and streams out
+/// the matched documents in `format`. `Csv`/`Ndjson` stream one plain-text
+/// line per document as they arrive; `Zip` has to finish building the whole
+/// archive first (zip's central directory can't be written incrementally),
+/// then streams it back out as base64-encoded text chunks.
+pub async fn export_search_results(query: SearchQuery, format: ExportFormat) -> anyhow::Result<impl Stream<Item = anyhow::Result<String>>> {
+    let stream: std::pin::Pin<Box<dyn Stream<Item = anyhow::Result<String>> + Send>> = match format {
+        ExportFormat::Csv => {
+            let header = futures::stream::once(async { anyhow::Ok("collection_dataset,file_hash,title\n".to_string()) });
+            Box::pin(header.chain(walk_all_results(query).map(|item| item.map(|item| csv_row(&item)))))
+        }
+        ExportFormat::Ndjson => Box::pin(walk_all_results(query).map(|item| {
+            item.and_then(|item| anyhow::Ok(format!("{}\n", serde_json::to_string(&item)?)))
+        })),
+        ExportFormat::Zip => {
+            let chunks = build_zip_chunks(query).await?;
+            Box::pin(futures::stream::iter(chunks))
+        }
+    };
+    Ok(stream)
+}
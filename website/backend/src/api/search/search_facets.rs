@@ -3,19 +3,215 @@
 use std::{collections::{HashMap, HashSet}, u64};
 
 use crate::{api::search::search_sql::build_sql_where_clause, db_utils::{clickhouse_utils::get_clickhouse_client, manticore_utils::{RawSearchResultAggregation, manticore_search_sql}}};
-use common::{search_query::SearchQuery, search_result::{FacetOriginalValue, SearchResultFacetItem, SearchResultFacets}};
+use common::{search_query::SearchQuery, search_result::{FacetOriginalValue, SearchResultFacetItem, SearchResultFacetStats, SearchResultFacets}};
 use serde::{Deserialize, Serialize};
 use crate::api::search::search_sql::{SQL_FROM_CLAUSE, SQL_OPTIONS_CLAUSE};
 
-pub async fn search_string_facet(mut query: SearchQuery, column: String, map_string_terms: Option<String>) -> anyhow::Result<SearchResultFacets> {
+/// Candidate pool for [`search_facet_values`]: much larger than
+/// [`common::search_const::FACET_VALUES_FETCH_CAP`], since a type-ahead
+/// match can sit far outside the top buckets by document count that
+/// `search_string_facet` normally shows.
+const FACET_SEARCH_FETCH_CAP: u64 = 5000;
+
+/// Meilisearch-style `/facet-search`: returns facet buckets for `column`
+/// whose display string matches `facet_query` (substring/prefix), reachable
+/// even when the value is far outside the top-by-count buckets that
+/// `search_string_facet` normally shows, so type-ahead works on
+/// high-cardinality facets like email addresses or filenames.
+pub async fn search_facet_values(
+    mut query: SearchQuery,
+    column: String,
+    facet_query: String,
+    map_string_terms: Option<String>,
+) -> anyhow::Result<SearchResultFacets> {
+    // remove all filters on current column, as we don't want to filter out unselected values from the facet
+    query.facet_filters.remove(&column);
+
+    if let Some(map_string_terms) = map_string_terms {
+        return search_facet_values_mapped(query, column, facet_query, map_string_terms).await;
+    }
+
+    // Plain string columns have no ClickHouse-side id table to pre-filter
+    // with, so the candidate pool is just widened and filtered in-process.
+    let sql_where_clause = build_sql_where_clause(&query)?;
+    let sql = format!(
+        "
+        SELECT file_hash
+        {SQL_FROM_CLAUSE}
+        {sql_where_clause}
+        LIMIT 0
+
+        {SQL_OPTIONS_CLAUSE}
+
+        FACET {column} DISTINCT file_hash ORDER BY count(distinct file_hash) DESC LIMIT {FACET_SEARCH_FETCH_CAP}
+        ;"
+    );
+    let facets = manticore_search_sql::<serde_json::Value>(sql).await?;
+    let facets = facets.aggregations.unwrap_or_default();
+    let facets = facets.get(&column).unwrap_or(&RawSearchResultAggregation::default()).buckets.clone();
+
+    let mut result = SearchResultFacets {
+        query: query.clone(),
+        facet_field: column.clone(),
+        facet_values: Vec::new(),
+        has_more: false,
+    };
+
+    if facets.is_empty() {
+        return Ok(result);
+    }
+
+    let mut response = facets.into_iter().map(|bucket| (bucket.key, bucket.doc_count)).collect::<Vec<_>>();
+    response.sort_by_key(|(_v, count)| u64::MAX - *count);
+    let mut present_values = HashSet::new();
+    for (value, count) in response {
+        if present_values.contains(&value) {
+            continue;
+        }
+        present_values.insert(value.clone());
+        result.facet_values.push(SearchResultFacetItem {
+            display_string: match &value {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Number(n) => n.as_u64().unwrap_or(0).to_string(),
+                _ => anyhow::bail!("Invalid value from manticore related to facets: {:#?}", value),
+            },
+            original_value: match &value {
+                serde_json::Value::String(s) => FacetOriginalValue::String(s.clone()),
+                serde_json::Value::Number(n) => FacetOriginalValue::Int(n.as_u64().unwrap_or(0)),
+                _ => anyhow::bail!("Invalid value from manticore related to facets: {:#?}", value),
+            },
+            count: count,
+        });
+    }
+    drop(present_values);
+
+    result.facet_values.sort_by_key(|item| (u64::MAX - item.count, item.display_string.clone()));
+    apply_facet_search_text_and_limit(&mut result, Some(facet_query), common::search_const::FACET_VALUES_PAGE_SIZE);
+
+    Ok(result)
+}
+
+/// The MVA/int-mapped side of [`search_facet_values`]: narrows candidate
+/// term ids in ClickHouse with a `term_value LIKE` predicate first, then
+/// restricts the Manticore facet query to just those ids, instead of
+/// filtering display strings after the fact (which `search_string_facet`
+/// can't do, since those strings aren't resolved until after the top-N
+/// buckets by count are already chosen).
+async fn search_facet_values_mapped(
+    query: SearchQuery,
+    column: String,
+    facet_query: String,
+    map_string_terms: String,
+) -> anyhow::Result<SearchResultFacets> {
+    let mut result = SearchResultFacets {
+        query: query.clone(),
+        facet_field: column.clone(),
+        facet_values: Vec::new(),
+        has_more: false,
+    };
+
+    let matching_ints = fetch_db_term_ids_matching(&facet_query, &map_string_terms).await?;
+    if matching_ints.is_empty() {
+        return Ok(result);
+    }
+    let matching_ints_set: HashSet<u64> = matching_ints.iter().copied().collect();
+    let ids_list = matching_ints.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(",");
+
+    let sql_where_clause = build_sql_where_clause(&query)?;
+    let sql = format!(
+        "
+        SELECT groupby() term, count(distinct file_hash) as doc_count
+        {SQL_FROM_CLAUSE}
+        {sql_where_clause}
+        AND {column} IN ({ids_list})
+
+        GROUP BY {column}
+        ORDER BY doc_count DESC LIMIT {FACET_SEARCH_FETCH_CAP}
+        ;"
+    );
+    let facets = manticore_search_sql::<SearchMvaFacetResponse>(sql).await?;
+    let facets = facets.hits.hits;
+
+    let mut response = facets.into_iter().map(|bucket| (bucket._source.term, bucket._source.doc_count)).collect::<Vec<_>>();
+    response.sort_by_key(|(_v, count)| u64::MAX - *count);
+    let mut present_values = HashSet::new();
+    for (value, count) in response {
+        // The MVA column is filtered by `IN`, not restricted to it, so a
+        // matching document's other (non-matching) values can still show
+        // up in the grouping; drop anything outside the ClickHouse match.
+        let is_requested_id = matches!(&value, serde_json::Value::Number(n) if n.as_u64().map_or(false, |i| matching_ints_set.contains(&i)));
+        if !is_requested_id || present_values.contains(&value) {
+            continue;
+        }
+        present_values.insert(value.clone());
+        result.facet_values.push(SearchResultFacetItem {
+            display_string: match &value {
+                serde_json::Value::Number(n) => n.as_u64().unwrap_or(0).to_string(),
+                _ => anyhow::bail!("Invalid value from manticore related to facets: {:#?}", value),
+            },
+            original_value: match &value {
+                serde_json::Value::Number(n) => FacetOriginalValue::Int(n.as_u64().unwrap_or(0)),
+                _ => anyhow::bail!("Invalid value from manticore related to facets: {:#?}", value),
+            },
+            count: count,
+        });
+    }
+    drop(present_values);
+
+    let ints = result.facet_values.iter().filter_map(|item| match item.original_value {
+        FacetOriginalValue::Int(i) => Some(i),
+        _ => None,
+    }).collect();
+    let display_strings = fetch_db_terms_for_ints(ints, map_string_terms).await?;
+    for item in &mut result.facet_values {
+        if let FacetOriginalValue::Int(i) = item.original_value {
+            if let Some(display_string) = display_strings.get(&i) {
+                item.display_string = display_string.clone();
+            }
+        }
+    }
+
+    result.facet_values.sort_by_key(|item| (u64::MAX - item.count, item.display_string.clone()));
+    apply_facet_search_text_and_limit(&mut result, None, common::search_const::FACET_VALUES_PAGE_SIZE);
+
+    Ok(result)
+}
+
+async fn fetch_db_term_ids_matching(facet_query: &str, field_name: &str) -> anyhow::Result<Vec<u64>> {
+    let client = get_clickhouse_client();
+    let sql = "
+    SELECT term_id
+    FROM string_term_id_to_text
+    WHERE term_field = ?
+      AND term_value LIKE ?
+    LIMIT ?
+    ";
+    let like_pattern = format!("%{}%", facet_query);
+    let result = client
+        .query(sql)
+        .bind(field_name)
+        .bind(like_pattern)
+        .bind(FACET_SEARCH_FETCH_CAP)
+        .fetch_all::<u64>()
+        .await?;
+    Ok(result)
+}
+
+pub async fn search_string_facet(
+    mut query: SearchQuery,
+    column: String,
+    map_string_terms: Option<String>,
+    facet_search_text: Option<String>,
+    limit: u64,
+) -> anyhow::Result<SearchResultFacets> {
 
     if map_string_terms.is_some() {
-        return search_mva_facet(query, column, map_string_terms).await;
+        return search_mva_facet(query, column, map_string_terms, facet_search_text, limit).await;
     }
     // remove all filters on current column, as we don't want to filter out unselected values from the facet
     query.facet_filters.remove(&column);
 
-    let sql_where_clause = build_sql_where_clause(&query);
+    let sql_where_clause = build_sql_where_clause(&query)?;
     let sql = format!(
         "
         SELECT file_hash
@@ -24,10 +220,11 @@ pub async fn search_string_facet(mut query: SearchQuery, column: String, map_str
         LIMIT 0
 
         {SQL_OPTIONS_CLAUSE}
-        
-        FACET {} DISTINCT file_hash ORDER BY count(distinct file_hash) DESC LIMIT 21
+
+        FACET {} DISTINCT file_hash ORDER BY count(distinct file_hash) DESC LIMIT {}
         ;",
         column,
+        common::search_const::FACET_VALUES_FETCH_CAP,
     );
     let facets = manticore_search_sql::<serde_json::Value>(sql).await?;
     let facets = facets.aggregations.unwrap_or_default();
@@ -37,6 +234,7 @@ pub async fn search_string_facet(mut query: SearchQuery, column: String, map_str
         query: query.clone(),
         facet_field: column.clone(),
         facet_values: Vec::new(),
+        has_more: false,
     };
 
     if facets.is_empty() {
@@ -84,10 +282,28 @@ pub async fn search_string_facet(mut query: SearchQuery, column: String, map_str
         }
     }
     result.facet_values.sort_by_key(|item| (u64::MAX - item.count, item.display_string.clone()));
+    apply_facet_search_text_and_limit(&mut result, facet_search_text, limit);
 
     Ok(result)
 }
 
+/// Filters the already-fetched, already-sorted facet values down to those
+/// matching `facet_search_text` (case-insensitive substring), then truncates
+/// to `limit`, setting `has_more` if anything was cut off. Filtering happens
+/// in-process, against the bucket list the caller already pulled (up to
+/// `FACET_VALUES_FETCH_CAP`, or `FACET_SEARCH_FETCH_CAP` for
+/// `search_facet_values`), rather than re-querying Manticore per keystroke.
+fn apply_facet_search_text_and_limit(result: &mut SearchResultFacets, facet_search_text: Option<String>, limit: u64) {
+    if let Some(search_text) = facet_search_text.as_ref().map(|s| s.trim().to_lowercase()).filter(|s| !s.is_empty()) {
+        result.facet_values.retain(|item| item.display_string.to_lowercase().contains(&search_text));
+    }
+    let limit = limit as usize;
+    if result.facet_values.len() > limit {
+        result.has_more = true;
+        result.facet_values.truncate(limit);
+    }
+}
+
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct SearchMvaFacetResponse {
@@ -95,11 +311,11 @@ struct SearchMvaFacetResponse {
     doc_count: u64,
 }
 
-pub async fn search_mva_facet(mut query: SearchQuery, column: String, map_string_terms: Option<String>) -> anyhow::Result<SearchResultFacets> {
+pub async fn search_mva_facet(mut query: SearchQuery, column: String, map_string_terms: Option<String>, facet_search_text: Option<String>, limit: u64) -> anyhow::Result<SearchResultFacets> {
     // remove all filters on current column, as we don't want to filter out unselected values from the facet
     query.facet_filters.remove(&column);
 
-    let sql_where_clause = build_sql_where_clause(&query);
+    let sql_where_clause = build_sql_where_clause(&query)?;
     let sql = format!(
         "
         SELECT groupby() term, count(distinct file_hash) as doc_count
@@ -107,9 +323,10 @@ pub async fn search_mva_facet(mut query: SearchQuery, column: String, map_string
         {sql_where_clause}
 
         GROUP BY {}
-        ORDER BY doc_count DESC LIMIT 21
+        ORDER BY doc_count DESC LIMIT {}
         ;",
         column,
+        common::search_const::FACET_VALUES_FETCH_CAP,
     );
     println!("sql: {}", sql);
     let facets = manticore_search_sql::<SearchMvaFacetResponse>(sql).await?;
@@ -119,6 +336,7 @@ pub async fn search_mva_facet(mut query: SearchQuery, column: String, map_string
         query: query.clone(),
         facet_field: column.clone(),
         facet_values: Vec::new(),
+        has_more: false,
     };
 
     if facets.is_empty() {
@@ -168,6 +386,7 @@ pub async fn search_mva_facet(mut query: SearchQuery, column: String, map_string
         }
     }
     result.facet_values.sort_by_key(|item| (u64::MAX - item.count, item.display_string.clone()));
+    apply_facet_search_text_and_limit(&mut result, facet_search_text, limit);
 
     Ok(result)
 }
@@ -184,3 +403,78 @@ async fn fetch_db_terms_for_ints(ints: Vec<u64>, field_name: String) -> anyhow::
     let result = client.query(sql).bind(field_name).bind(ints).fetch_all::<(u64, String)>().await?;
     Ok(HashMap::from_iter(result))
 }
+
+/// Number of equal-width buckets [`search_numeric_facet_stats`] partitions a
+/// numeric column's `[min, max]` range into for its histogram.
+const NUMERIC_FACET_HISTOGRAM_BUCKETS: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct NumericFacetRange {
+    mn: f64,
+    mx: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct NumericFacetBucket {
+    bucket: u32,
+    doc_count: u64,
+}
+
+/// Meilisearch-style facet stats/distribution for a numeric `column`: its
+/// `[min, max]` range under the current query, plus a
+/// `NUMERIC_FACET_HISTOGRAM_BUCKETS`-bucket histogram across that range, so
+/// a client can render a range slider with a distribution instead of the
+/// discrete top-N bucket list `search_string_facet`/`search_mva_facet`
+/// return. Issues `MIN`/`MAX` first, then partitions `[min, max]` into equal
+/// width buckets with Manticore's `INTERVAL()`/`GROUP BY`.
+pub async fn search_numeric_facet_stats(mut query: SearchQuery, column: String) -> anyhow::Result<SearchResultFacetStats> {
+    query.facet_filters.remove(&column);
+    let sql_where_clause = build_sql_where_clause(&query)?;
+
+    let range_sql = format!(
+        "
+        SELECT MIN({column}) mn, MAX({column}) mx
+        {SQL_FROM_CLAUSE}
+        {sql_where_clause}
+        ;"
+    );
+    let range = manticore_search_sql::<NumericFacetRange>(range_sql).await?;
+    let Some(range) = range.hits.hits.into_iter().next().map(|hit| hit._source) else {
+        return Ok(SearchResultFacetStats { facet_field: column, min: 0.0, max: 0.0, histogram: Vec::new() });
+    };
+
+    if range.mx <= range.mn {
+        return Ok(SearchResultFacetStats { facet_field: column, min: range.mn, max: range.mx, histogram: Vec::new() });
+    }
+
+    let bucket_width = (range.mx - range.mn) / NUMERIC_FACET_HISTOGRAM_BUCKETS as f64;
+    let bucket_bounds = (1..NUMERIC_FACET_HISTOGRAM_BUCKETS)
+        .map(|i| (range.mn + bucket_width * i as f64).to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let histogram_sql = format!(
+        "
+        SELECT INTERVAL({column}, {bucket_bounds}) as bucket, count(distinct file_hash) as doc_count
+        {SQL_FROM_CLAUSE}
+        {sql_where_clause}
+        GROUP BY bucket
+        ;"
+    );
+    let histogram = manticore_search_sql::<NumericFacetBucket>(histogram_sql).await?;
+
+    let mut doc_counts = vec![0u64; NUMERIC_FACET_HISTOGRAM_BUCKETS as usize];
+    for hit in histogram.hits.hits {
+        if let Some(count) = doc_counts.get_mut(hit._source.bucket as usize) {
+            *count = hit._source.doc_count;
+        }
+    }
+
+    let histogram = doc_counts.into_iter().enumerate().map(|(i, doc_count)| {
+        let low = range.mn + bucket_width * i as f64;
+        let high = if i as u32 + 1 == NUMERIC_FACET_HISTOGRAM_BUCKETS { range.mx } else { low + bucket_width };
+        (low, high, doc_count)
+    }).collect();
+
+    Ok(SearchResultFacetStats { facet_field: column, min: range.mn, max: range.mx, histogram })
+}
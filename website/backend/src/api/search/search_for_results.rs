@@ -1,13 +1,33 @@
 //! Search endpoint for result lists.
 
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use futures::{Stream, StreamExt};
+
 use common::{
     search_query::SearchQuery,
-    search_result::{DocumentIdentifier, SearchResultDocumentItem, SearchResultDocuments},
+    search_result::{DocumentIdentifier, SearchResultDocumentItem, SearchResultDocuments, SearchResultSnippet},
 };
 use serde::{Deserialize, Serialize};
-use crate::api::search::search_sql::{SQL_FROM_CLAUSE, build_sql_where_clause, SQL_OPTIONS_CLAUSE};
+use crate::api::search::search_embed::embed_query_text;
+use crate::api::search::search_sql::{SQL_FROM_CLAUSE, build_knn_where_clause, build_knn_where_clause_for_collection, build_sql_where_clause, build_sql_where_clause_for_collection, highlight_field_sql, sql_options_clause};
 use crate::{db_utils::{decompose_spans::decompose_text_into_spans, manticore_utils::manticore_search_sql}};
 
+/// Number of per-document snippet queries allowed to run concurrently while
+/// streaming phase two, so a large page fills in steadily rather than
+/// hammering Manticore with every document's `HIGHLIGHT` query at once.
+const SNIPPET_STREAM_CONCURRENCY: usize = 4;
+
+/// Candidate pool pulled from each side (keyword / vector) of hybrid search
+/// before fusing, mirroring Meilisearch's hybrid search candidate pool.
+const HYBRID_CANDIDATE_POOL: u64 = 200;
+
+/// Reciprocal Rank Fusion's rank-dampening constant: higher values flatten
+/// out how much a document's exact rank (as opposed to just appearing near
+/// the top) affects its fused score.
+const RRF_K: f64 = 60.0;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct SearchForResultsResponse {
     collection_dataset: String,
@@ -15,30 +35,45 @@ struct SearchForResultsResponse {
     page_ids: String,
     filenames: String,
 
-    highlight_text: String,
-    highlight_filenames: String,
-
     file_types: Vec<u64>,
     // file_mime_types: Vec<u64>,
     // file_extensions: Vec<u64>,
     // file_paths: Vec<u64>,
 }
 
-pub async fn search_for_results(query: SearchQuery, current_search_result_page: u64) -> anyhow::Result<SearchResultDocuments> {
-    let sql_where_clause = build_sql_where_clause(&query);
-    let mut offset = current_search_result_page * common::search_const::PAGE_SIZE;
-    let mut limit = common::search_const::PAGE_SIZE + 1;
-    let mut drop_first = false;
-    if current_search_result_page > 0 {
-        drop_first = true;
-        offset -= 1;
-        limit += 1;
-    }
+/// A single document hit from a shard query, still carrying its BM25 weight
+/// so hits from different shards can be merged by descending score instead
+/// of by the per-shard arrival order.
+struct ShardHit {
+    score: u64,
+    item: SearchResultDocumentItem,
+}
 
-    // tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+/// Runs the result-page query against one shard (all of Manticore when
+/// `collection_dataset` is `None`, or a single collection's slice of the
+/// index otherwise, whether that's the only collection named or one of
+/// several being federated), windowed by `offset`/`limit`. Dispatches to
+/// the plain keyword path or, when `query.semantic_ratio` calls for it, the
+/// hybrid keyword+vector path.
+async fn query_shard(query: &SearchQuery, collection_dataset: Option<&str>, offset: u64, limit: u64) -> anyhow::Result<(Vec<ShardHit>, bool, bool, u32)> {
+    let semantic_ratio = query.semantic_ratio.unwrap_or(0.0).clamp(0.0, 1.0);
+    let sql_where_clause = match collection_dataset {
+        Some(collection_dataset) => build_sql_where_clause_for_collection(query, collection_dataset)?,
+        None => build_sql_where_clause(query)?,
+    };
 
+    if semantic_ratio <= 0.0 {
+        return query_shard_keyword(query, &sql_where_clause, offset, limit).await;
+    }
 
+    query_shard_hybrid(query, collection_dataset, &sql_where_clause, offset, limit, semantic_ratio).await
+}
 
+/// The pure full-text side of [`query_shard`]: `semantic_ratio <= 0.0`
+/// resolves straight to this, and hybrid mode also uses it for the keyword
+/// half of its candidate pool.
+async fn query_shard_keyword(query: &SearchQuery, sql_where_clause: &str, offset: u64, limit: u64) -> anyhow::Result<(Vec<ShardHit>, bool, bool, u32)> {
+    let sql_options_clause = sql_options_clause(query);
     let sql = format!(
         "
     SELECT collection_dataset,
@@ -46,25 +81,6 @@ pub async fn search_for_results(query: SearchQuery, current_search_result_page:
         group_concat(page_id) AS page_ids,
         doc_metadata.filenames as filenames,
 
-        HIGHLIGHT({{
-            limit=400,
-            limit_words=100,
-            limit_snippets=1,
-            html_strip_mode=strip,
-            before_match='<hoover4_strong>',
-            after_match='</hoover4_strong>',
-            around=50
-        }}, page_text) as highlight_text,
-        HIGHLIGHT({{
-            limit=400,
-            limit_words=100,
-            limit_snippets=1,
-            html_strip_mode=strip,
-            before_match='<hoover4_strong>',
-            after_match='</hoover4_strong>',
-            around=50
-        }}, filenames) as highlight_filenames,
-
         doc_metadata.file_types as file_types
 
     {SQL_FROM_CLAUSE}
@@ -74,7 +90,7 @@ pub async fn search_for_results(query: SearchQuery, current_search_result_page:
     GROUP BY file_hash
     LIMIT {limit} OFFSET {offset}
 
-    {SQL_OPTIONS_CLAUSE}
+    {sql_options_clause}
     ;",
     /*
     ,
@@ -90,46 +106,218 @@ pub async fn search_for_results(query: SearchQuery, current_search_result_page:
         */
     );
     let response = manticore_search_sql::<SearchForResultsResponse>(sql).await?;
+    let timed_out = response.timed_out;
+    let cache_hit = response.cache_hit;
+    let duration_ms = response.duration_ms;
 
-    let mut search_results = response
+    let hits = response
         .hits
         .hits
-        .into_iter().enumerate()
-        .map(|(hit_index_in_page, hit)| {
-
-            let filenames = hit._source.filenames.split("\n").map(|i| i.to_string()).collect::<Vec<_>>();
-            let mut title =hit
-            ._source
-            .filenames
-            .split("\n")
-            .next()
-            .unwrap_or("")
-            .to_string();
-
-            if !hit._source.filenames.is_empty() {
-                for x in filenames {
-                    if x.contains("<strong>") {
-                        title = x.clone();
-                    }
-                }
-            }
-
-            SearchResultDocumentItem {
-            collection_dataset: hit._source.collection_dataset,
-            file_hash: hit._source.file_hash,
-            title: hit
+        .into_iter()
+        .map(|hit| {
+            let title = hit
                 ._source
                 .filenames
                 .split("\n")
                 .next()
                 .unwrap_or("")
-                .to_string(),
-            highlight_text_spans: decompose_text_into_spans(hit._source.highlight_text),
-            highlight_filenames_spans: decompose_text_into_spans(title.clone()),
-            result_index_in_page: 0_u64,
-        }})
+                .to_string();
+
+            ShardHit {
+                score: hit._score,
+                item: SearchResultDocumentItem {
+                    collection_dataset: hit._source.collection_dataset,
+                    file_hash: hit._source.file_hash,
+                    title,
+                    highlight_text_spans: vec![],
+                    highlight_filenames_spans: vec![],
+                    snippets_loaded: false,
+                    result_index_in_page: 0_u64,
+                },
+            }
+        })
         .collect::<Vec<_>>();
 
+    Ok((hits, timed_out, cache_hit, duration_ms))
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SearchShardKnnResponse {
+    collection_dataset: String,
+    file_hash: String,
+    filenames: String,
+    #[serde(rename = "knn_dist()")]
+    knn_dist: f32,
+}
+
+/// The vector-similarity side of hybrid search: runs `knn_where_clause`
+/// against an embedding of the query text and returns hits in ascending
+/// distance order (closest/best first), de-duplicated by `file_hash` the
+/// same way [`crate::api::search::search_similar::search_similar`] is.
+async fn query_shard_knn(knn_where_clause: &str, limit: u64) -> anyhow::Result<(Vec<ShardHit>, bool, bool, u32)> {
+    let sql = format!(
+        "
+    SELECT collection_dataset,
+        file_hash,
+        doc_metadata.filenames as filenames,
+        knn_dist()
+
+    {SQL_FROM_CLAUSE}
+
+    {knn_where_clause}
+
+    ORDER BY knn_dist() ASC
+    LIMIT {limit}
+    ;"
+    );
+    let response = manticore_search_sql::<SearchShardKnnResponse>(sql).await?;
+    let timed_out = response.timed_out;
+    let cache_hit = response.cache_hit;
+    let duration_ms = response.duration_ms;
+
+    // knn() ranks by page, not by document; keep only each document's
+    // closest (smallest-distance) page.
+    let mut best_per_document: BTreeMap<String, SearchShardKnnResponse> = BTreeMap::new();
+    for hit in response.hits.hits {
+        let source = hit._source;
+        best_per_document
+            .entry(source.file_hash.clone())
+            .and_modify(|existing| {
+                if source.knn_dist < existing.knn_dist {
+                    *existing = source.clone();
+                }
+            })
+            .or_insert(source);
+    }
+    let mut ordered = best_per_document.into_values().collect::<Vec<_>>();
+    ordered.sort_by(|a, b| a.knn_dist.partial_cmp(&b.knn_dist).unwrap_or(Ordering::Equal));
+
+    let hits = ordered
+        .into_iter()
+        .map(|item| {
+            let title = item.filenames.split("\n").next().unwrap_or("").to_string();
+            ShardHit {
+                // Unused by the hybrid fusion below, which ranks by RRF
+                // rank rather than this raw score.
+                score: 0,
+                item: SearchResultDocumentItem {
+                    collection_dataset: item.collection_dataset,
+                    file_hash: item.file_hash,
+                    title,
+                    highlight_text_spans: vec![],
+                    highlight_filenames_spans: vec![],
+                    snippets_loaded: false,
+                    result_index_in_page: 0_u64,
+                },
+            }
+        })
+        .collect();
+
+    Ok((hits, timed_out, cache_hit, duration_ms))
+}
+
+/// Blends [`query_shard_keyword`] and [`query_shard_knn`] via Reciprocal
+/// Rank Fusion: `score = (1 - semantic_ratio) / (RRF_K + rank_keyword) +
+/// semantic_ratio / (RRF_K + rank_vector)`, with a document missing from
+/// one side contributing 0 from it. Mirrors Meilisearch's hybrid search.
+async fn query_shard_hybrid(
+    query: &SearchQuery,
+    collection_dataset: Option<&str>,
+    sql_where_clause: &str,
+    offset: u64,
+    limit: u64,
+    semantic_ratio: f32,
+) -> anyhow::Result<(Vec<ShardHit>, bool, bool, u32)> {
+    let (keyword_hits, keyword_timed_out, keyword_cache_hit, keyword_duration_ms) =
+        query_shard_keyword(query, sql_where_clause, 0, HYBRID_CANDIDATE_POOL).await?;
+
+    let query_vector = embed_query_text(&query.query_string).await?;
+    let vector_literal = query_vector.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",");
+    let knn_where_clause = match collection_dataset {
+        Some(collection_dataset) => build_knn_where_clause_for_collection(query, &vector_literal, HYBRID_CANDIDATE_POOL, collection_dataset),
+        None => build_knn_where_clause(query, &vector_literal, HYBRID_CANDIDATE_POOL),
+    };
+    let (vector_hits, vector_timed_out, vector_cache_hit, vector_duration_ms) =
+        query_shard_knn(&knn_where_clause, HYBRID_CANDIDATE_POOL).await?;
+
+    let semantic_ratio = semantic_ratio as f64;
+    let mut fused_scores: BTreeMap<(String, String), f64> = BTreeMap::new();
+    let mut fused_items: BTreeMap<(String, String), SearchResultDocumentItem> = BTreeMap::new();
+    for (rank, hit) in keyword_hits.into_iter().enumerate() {
+        let key = (hit.item.collection_dataset.clone(), hit.item.file_hash.clone());
+        *fused_scores.entry(key.clone()).or_insert(0.0) += (1.0 - semantic_ratio) / (RRF_K + rank as f64 + 1.0);
+        fused_items.entry(key).or_insert(hit.item);
+    }
+    for (rank, hit) in vector_hits.into_iter().enumerate() {
+        let key = (hit.item.collection_dataset.clone(), hit.item.file_hash.clone());
+        *fused_scores.entry(key.clone()).or_insert(0.0) += semantic_ratio / (RRF_K + rank as f64 + 1.0);
+        fused_items.entry(key).or_insert(hit.item);
+    }
+
+    let mut ranked = fused_scores.into_iter().collect::<Vec<_>>();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+
+    let hits = ranked
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .filter_map(|(key, fused_score)| {
+            fused_items.remove(&key).map(|item| ShardHit {
+                // Scaled up so it stays comparable to the federated merge's
+                // BM25-scale sort below; only the relative order matters.
+                score: (fused_score * 1_000_000.0) as u64,
+                item,
+            })
+        })
+        .collect();
+
+    Ok((
+        hits,
+        keyword_timed_out || vector_timed_out,
+        keyword_cache_hit && vector_cache_hit,
+        keyword_duration_ms.max(vector_duration_ms),
+    ))
+}
+
+pub async fn search_for_results(query: SearchQuery, current_search_result_page: u64) -> anyhow::Result<SearchResultDocuments> {
+    let mut offset = current_search_result_page * common::search_const::PAGE_SIZE;
+    let mut limit = common::search_const::PAGE_SIZE + 1;
+    let mut drop_first = false;
+    if current_search_result_page > 0 {
+        drop_first = true;
+        offset -= 1;
+        limit += 1;
+    }
+
+    // tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+    let (shard_hits, timed_out, cache_hit, cache_duration_ms) = if query.collection_datasets.len() > 1 {
+        // Federated mode: each shard can't know in advance how many of its
+        // own hits fall inside the global [offset, offset+limit) window, so
+        // every shard is asked for its own top `offset + limit` hits; the
+        // merge below then re-derives the real window from the combined,
+        // re-sorted sequence.
+        let per_shard_limit = offset + limit;
+        let shard_results = futures::future::try_join_all(query.collection_datasets.iter().map(|collection_dataset| {
+            query_shard(&query, Some(collection_dataset), 0, per_shard_limit)
+        })).await?;
+
+        // A federated page is only as complete as its slowest shard, so the
+        // whole page is flagged as timed out if any shard was, and it's only
+        // an all-cache hit if every shard was served from cache.
+        let timed_out = shard_results.iter().any(|(_, timed_out, _, _)| *timed_out);
+        let cache_hit = shard_results.iter().all(|(_, _, cache_hit, _)| *cache_hit);
+        let cache_duration_ms = shard_results.iter().map(|(_, _, _, duration_ms)| *duration_ms).max().unwrap_or(0);
+        let mut merged = shard_results.into_iter().flat_map(|(hits, _, _, _)| hits).collect::<Vec<_>>();
+        merged.sort_by(|a, b| b.score.cmp(&a.score));
+        (merged.into_iter().skip(offset as usize).take(limit as usize).collect::<Vec<_>>(), timed_out, cache_hit, cache_duration_ms)
+    } else {
+        let collection_dataset = query.collection_datasets.first().map(|s| s.as_str());
+        query_shard(&query, collection_dataset, offset, limit).await?
+    };
+
+    let mut search_results = shard_hits.into_iter().map(|hit| hit.item).collect::<Vec<_>>();
+
     let mut prev_hash = None;
     if drop_first {
         prev_hash = Some(DocumentIdentifier {
@@ -158,6 +346,85 @@ pub async fn search_for_results(query: SearchQuery, current_search_result_page:
         prev_hash,
         next_hash,
         page_number: current_search_result_page,
+        timed_out,
+        cache_hit,
+        cache_duration_ms,
     };
     Ok(result)
 }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SearchSnippetSqlResponse {
+    collection_dataset: String,
+    file_hash: String,
+    page_id: u32,
+    highlight_text: String,
+    highlight_filenames: String,
+}
+
+async fn fetch_snippet_for_document(query: &SearchQuery, document_identifier: &DocumentIdentifier) -> anyhow::Result<SearchResultSnippet> {
+    let highlight_text_sql = highlight_field_sql(query, "page_text", "highlight_text");
+    let highlight_filenames_sql = highlight_field_sql(query, "filenames", "highlight_filenames");
+    let sql_options_clause = sql_options_clause(query);
+    // No `GROUP BY`: `page_id` has to come from the exact row `HIGHLIGHT()`
+    // ran against, not an aggregate over the whole document, or the two can
+    // point at different pages. `ORDER BY WEIGHT() DESC LIMIT 1` picks that
+    // single best-matching row directly.
+    let sql_where_clause = build_sql_where_clause_for_collection(query, &document_identifier.collection_dataset)?;
+    let sql = format!(
+        "
+    SELECT collection_dataset,
+        file_hash,
+
+        page_id,
+
+        {highlight_text_sql},
+        {highlight_filenames_sql}
+
+    {SQL_FROM_CLAUSE}
+
+    {sql_where_clause}
+        AND file_hash = {}
+
+    ORDER BY WEIGHT() DESC
+    LIMIT 1
+
+    {sql_options_clause}
+    ;",
+        format_sql_query::QuotedData(&document_identifier.file_hash),
+    );
+    let response = manticore_search_sql::<SearchSnippetSqlResponse>(sql).await?;
+    let hit = response.hits.hits.into_iter().next()
+        .ok_or_else(|| anyhow::anyhow!("document not found: {}/{}", document_identifier.collection_dataset, document_identifier.file_hash))?;
+
+    let title = hit._source.highlight_filenames.clone();
+    let page_id = hit._source.page_id;
+    let highlight_text_spans = decompose_text_into_spans(hit._source.highlight_text, &query.query_string, Some(page_id));
+    let match_count = highlight_text_spans.iter().filter(|span| span.is_highlighted).count() as u64;
+    Ok(SearchResultSnippet {
+        collection_dataset: hit._source.collection_dataset,
+        file_hash: hit._source.file_hash,
+        highlight_text_spans,
+        highlight_filenames_spans: decompose_text_into_spans(title, &query.query_string, None),
+        match_count,
+        page_id: Some(page_id),
+    })
+}
+
+/// Phase two of the two-phase search: re-derives the ordered document list
+/// for `current_search_result_page` the same way `search_for_results` does,
+/// then runs the expensive per-document `HIGHLIGHT` query a few documents at
+/// a time, yielding each [`SearchResultSnippet`] as soon as it completes so
+/// callers can fill in result cards progressively instead of blocking the
+/// whole page on the slowest document.
+pub async fn stream_search_snippets(query: SearchQuery, current_search_result_page: u64) -> anyhow::Result<impl Stream<Item = anyhow::Result<SearchResultSnippet>>> {
+    let page = search_for_results(query.clone(), current_search_result_page).await?;
+    let documents = page.results.iter().map(|result| result.document_identifier()).collect::<Vec<_>>();
+
+    Ok(futures::stream::iter(documents)
+        .map(move |document_identifier| {
+            let query = query.clone();
+            async move { fetch_snippet_for_document(&query, &document_identifier).await }
+        })
+        .buffer_unordered(SNIPPET_STREAM_CONCURRENCY))
+}
@@ -9,7 +9,7 @@ pub struct SearchForResultsHitCountResponse {
 }
 
 pub async fn search_for_results_hit_count(query: SearchQuery) -> anyhow::Result<u64> {
-    let sql_where_clause = build_sql_where_clause(&query);
+    let sql_where_clause = build_sql_where_clause(&query)?;
     let sql = format!(
         "
         SELECT count(distinct file_hash) as total_count
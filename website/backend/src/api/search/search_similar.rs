@@ -0,0 +1,108 @@
+//! "More like this" semantic-similarity lookup, built on Manticore KNN over
+//! a per-document embedding vector stored on `doc_text_pages`.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use common::search_result::{DocumentIdentifier, SearchResultDocumentItem};
+use serde::{Deserialize, Serialize};
+
+use crate::api::search::search_sql::SQL_FROM_CLAUSE;
+use crate::db_utils::manticore_utils::manticore_search_sql;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SeedEmbeddingResponse {
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SearchSimilarResponse {
+    collection_dataset: String,
+    file_hash: String,
+    filenames: String,
+    #[serde(rename = "knn_dist()")]
+    knn_dist: f32,
+}
+
+/// Returns up to `limit` documents ranked by embedding similarity to
+/// `document_identifier`'s own stored vector, for a "more like this"
+/// affordance on the document view page. Unlike `search_for_results`, this
+/// ranks by KNN distance rather than a text `query`, so there's no
+/// highlight snippet to compute.
+pub async fn search_similar(document_identifier: DocumentIdentifier, limit: u64) -> anyhow::Result<Vec<SearchResultDocumentItem>> {
+    let seed_sql = format!(
+        "SELECT embedding FROM doc_text_pages WHERE collection_dataset = {} AND file_hash = {} LIMIT 1;",
+        format_sql_query::QuotedData(&document_identifier.collection_dataset),
+        format_sql_query::QuotedData(&document_identifier.file_hash),
+    );
+    let seed_response = manticore_search_sql::<SeedEmbeddingResponse>(seed_sql).await?;
+    let seed_vector = seed_response
+        .hits
+        .hits
+        .into_iter()
+        .next()
+        .map(|hit| hit._source.embedding)
+        .ok_or_else(|| anyhow::anyhow!("no embedding stored for {}/{}", document_identifier.collection_dataset, document_identifier.file_hash))?;
+
+    // Ask for more neighbors than `limit`, since the seed document's own
+    // pages and repeat pages of an already-seen document are filtered out
+    // below before truncating down to `limit`.
+    let k = limit + 1;
+    let vector_literal = seed_vector.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",");
+    let sql = format!(
+        "
+    SELECT collection_dataset,
+        file_hash,
+        doc_metadata.filenames as filenames,
+        knn_dist()
+
+    {SQL_FROM_CLAUSE}
+
+    WHERE knn(embedding, {k}, ({vector_literal}))
+      AND collection_dataset = {}
+
+    ORDER BY knn_dist() ASC
+    ;",
+        format_sql_query::QuotedData(&document_identifier.collection_dataset),
+    );
+    let response = manticore_search_sql::<SearchSimilarResponse>(sql).await?;
+
+    // `knn()` ranks by page, not by document, so the same document can show
+    // up more than once; keep only its closest (smallest-distance) page.
+    let mut best_per_document: BTreeMap<String, SearchSimilarResponse> = BTreeMap::new();
+    for hit in response.hits.hits {
+        let source = hit._source;
+        if source.file_hash == document_identifier.file_hash {
+            continue;
+        }
+        best_per_document
+            .entry(source.file_hash.clone())
+            .and_modify(|existing| {
+                if source.knn_dist < existing.knn_dist {
+                    *existing = source.clone();
+                }
+            })
+            .or_insert(source);
+    }
+
+    let mut results = best_per_document.into_values().collect::<Vec<_>>();
+    results.sort_by(|a, b| a.knn_dist.partial_cmp(&b.knn_dist).unwrap_or(Ordering::Equal));
+    results.truncate(limit as usize);
+
+    Ok(results
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let title = item.filenames.split("\n").next().unwrap_or("").to_string();
+            SearchResultDocumentItem {
+                collection_dataset: item.collection_dataset,
+                file_hash: item.file_hash,
+                title,
+                highlight_text_spans: vec![],
+                highlight_filenames_spans: vec![],
+                snippets_loaded: false,
+                result_index_in_page: i as u64,
+            }
+        })
+        .collect())
+}
@@ -1,6 +1,6 @@
 //! SQL builder helpers for search queries.
 
-use common::{search_query::SearchQuery, search_result::FacetOriginalValue};
+use common::{search_const::{DEFAULT_CROP_LENGTH, DEFAULT_SNIPPET_AROUND}, search_query::{SearchQuery, SearchQueryMode}, search_result::FacetOriginalValue};
 
 pub const SQL_FROM_CLAUSE: &'static str = "
     FROM doc_text_pages
@@ -11,27 +11,166 @@ pub const SQL_FROM_CLAUSE: &'static str = "
 
 pub const SQL_OPTIONS_CLAUSE: &'static str = "OPTION agent_query_timeout=60000,max_query_time=60000";
 
+/// Same as [`SQL_OPTIONS_CLAUSE`], but honors `query.timeout_ms` instead of
+/// the hardcoded 60 second default, so a slow query returns whatever
+/// Manticore gathered in time (`RawSarchResult::timed_out`) instead of
+/// blocking the caller.
+pub fn sql_options_clause(query: &SearchQuery) -> String {
+    let timeout_ms = query.timeout_ms.unwrap_or(60_000);
+    format!("OPTION agent_query_timeout={timeout_ms},max_query_time={timeout_ms}")
+}
 
-pub fn build_sql_where_clause(query: &SearchQuery) -> String {
-    // automatically quote all @ symbols in the query string to avoid problems with FIELD SELECTOR manticore operator
-    let query_string = query.query_string.clone().trim().replace("@", "\\@");
 
-    let mut terms = vec![format!("
-        WHERE MATCH({}, doc_text_pages)
-    ", format_sql_query::QuotedData(&query_string))];
+pub fn build_sql_where_clause(query: &SearchQuery) -> anyhow::Result<String> {
+    let mut terms = vec![format!("WHERE {}", match_predicate(query)?)];
+    terms.extend(facet_filter_terms(query));
 
-    for (field_name, values) in query.facet_filters.iter() {
+    Ok(terms.join("
+        AND "))
+}
+
+/// Builds the text-match predicate (no `WHERE` prefix, no facet filters)
+/// honoring `query.query_mode`: `Keyword` is a plain bag-of-words `MATCH`,
+/// `Phrase` wraps the input in quotes for exact ordered matching, and
+/// `Regex` matches `query_string` as a pattern against the full-text columns
+/// via `REGEX()` instead of `MATCH()`. Returns an error rather than handing
+/// Manticore an unparsable pattern if `Regex` mode is given invalid regex.
+fn match_predicate(query: &SearchQuery) -> anyhow::Result<String> {
+    let query_string = query.query_string.trim();
+    match query.query_mode {
+        SearchQueryMode::Regex => {
+            regex::Regex::new(query_string).map_err(|e| anyhow::anyhow!("Invalid regex pattern: {e}"))?;
+            Ok(format!(
+                "(REGEX(page_text, {0}) OR REGEX(filenames, {0}))",
+                format_sql_query::QuotedData(query_string),
+            ))
+        }
+        SearchQueryMode::Keyword | SearchQueryMode::Phrase => {
+            // automatically quote all @ symbols in the query string to avoid problems with FIELD SELECTOR manticore operator
+            let escaped = query_string.replace("@", "\\@");
+            let match_text = if query.query_mode == SearchQueryMode::Phrase { format!("\"{escaped}\"") } else { escaped };
+            Ok(format!("MATCH({}, doc_text_pages)", format_sql_query::QuotedData(&match_text)))
+        }
+    }
+}
+
+/// Same as [`build_sql_where_clause`], but scopes the match to a single
+/// field via Manticore's `@field` selector instead of matching across the
+/// whole index. Used by the unified search preview to query content and
+/// filenames independently. Honors `query.query_mode` the same way
+/// [`match_predicate`] does, so switching modes in the search bar also
+/// changes what the preview dropdown matches.
+pub fn build_field_scoped_where_clause(query: &SearchQuery, field: &str) -> anyhow::Result<String> {
+    let query_string = query.query_string.trim();
+    let predicate = match query.query_mode {
+        SearchQueryMode::Regex => {
+            regex::Regex::new(query_string).map_err(|e| anyhow::anyhow!("Invalid regex pattern: {e}"))?;
+            format!("REGEX({field}, {})", format_sql_query::QuotedData(query_string))
+        }
+        SearchQueryMode::Keyword | SearchQueryMode::Phrase => {
+            // automatically quote all @ symbols in the query string to avoid problems with FIELD SELECTOR manticore operator
+            let escaped = query_string.replace("@", "\\@");
+            let match_text = if query.query_mode == SearchQueryMode::Phrase { format!("\"{escaped}\"") } else { escaped };
+            let field_scoped_query = format!("@{field} {match_text}");
+            format!("MATCH({}, doc_text_pages)", format_sql_query::QuotedData(&field_scoped_query))
+        }
+    };
+
+    let mut terms = vec![format!("WHERE {predicate}")];
+    terms.extend(facet_filter_terms(query));
+
+    Ok(terms.join("
+        AND "))
+}
+
+/// Scopes [`build_sql_where_clause`] to a single shard of a federated query
+/// by conjoining a `collection_dataset` filter, used to fan a `SearchQuery`
+/// targeting several `collection_datasets` out into one query per shard.
+pub fn build_sql_where_clause_for_collection(query: &SearchQuery, collection_dataset: &str) -> anyhow::Result<String> {
+    Ok(format!(
+        "{}
+        AND collection_dataset = {}",
+        build_sql_where_clause(query)?,
+        format_sql_query::QuotedData(collection_dataset),
+    ))
+}
+
+/// The vector-similarity counterpart of [`build_sql_where_clause`]: matches
+/// via Manticore's `knn()` operator against `vector_literal` instead of
+/// `MATCH()`, for the semantic side of hybrid search's Reciprocal Rank
+/// Fusion. Facet filters still apply to both sides equally.
+pub fn build_knn_where_clause(query: &SearchQuery, vector_literal: &str, k: u64) -> String {
+    let mut terms = vec![format!("WHERE knn(embedding, {k}, ({vector_literal}))")];
+    terms.extend(facet_filter_terms(query));
+
+    terms.join("
+        AND ")
+}
+
+/// Scopes [`build_knn_where_clause`] to a single shard of a federated query,
+/// the same way [`build_sql_where_clause_for_collection`] scopes the
+/// keyword side.
+pub fn build_knn_where_clause_for_collection(query: &SearchQuery, vector_literal: &str, k: u64, collection_dataset: &str) -> String {
+    format!(
+        "{}
+        AND collection_dataset = {}",
+        build_knn_where_clause(query, vector_literal, k),
+        format_sql_query::QuotedData(collection_dataset),
+    )
+}
+
+/// A facet-filters-only WHERE clause with no text match at all, for queries
+/// that narrow down by a facet value already known to match (e.g. the
+/// unified search metadata provider, which finds the facet value first and
+/// then fetches documents carrying it).
+pub fn build_facet_only_where_clause(query: &SearchQuery) -> String {
+    let terms = facet_filter_terms(query);
+    if terms.is_empty() {
+        return "WHERE 1=1".to_string();
+    }
+    format!("WHERE {}", terms.join("
+        AND "))
+}
+
+/// Builds the `SELECT` fragment for one field's snippet, either a Manticore
+/// `HIGHLIGHT()` call cropped to `query.crop_length` words (the default for
+/// any field not excluded via `attributes_to_crop`/`attributes_to_highlight`)
+/// or a plain passthrough of the stored field when the caller opted the
+/// field out of highlighting entirely.
+pub fn highlight_field_sql(query: &SearchQuery, field: &str, alias: &str) -> String {
+    let highlighted = query.attributes_to_highlight.as_ref().map_or(true, |fields| fields.iter().any(|f| f == field));
+    if !highlighted {
+        return format!("{field} as {alias}");
+    }
+
+    let cropped = query.attributes_to_crop.as_ref().map_or(true, |fields| fields.iter().any(|f| f == field));
+    let limit_words = if cropped { query.crop_length.unwrap_or(DEFAULT_CROP_LENGTH) } else { u32::MAX };
+    // `limit` bounds the fragment by characters; kept generous relative to
+    // `limit_words` so it only ever binds on pathologically long words.
+    let limit_chars = limit_words.saturating_mul(10).min(100_000);
+    let around = query.snippet_around.unwrap_or(DEFAULT_SNIPPET_AROUND);
+
+    format!(
+        "HIGHLIGHT({{
+            limit={limit_chars},
+            limit_words={limit_words},
+            limit_snippets=1,
+            html_strip_mode=strip,
+            before_match='<hoover4_strong>',
+            after_match='</hoover4_strong>',
+            around={around}
+        }}, {field}) as {alias}"
+    )
+}
+
+fn facet_filter_terms(query: &SearchQuery) -> Vec<String> {
+    query.facet_filters.iter().map(|(field_name, values)| {
         let values_str = values.iter().map(|value| {
             match value {
                 FacetOriginalValue::String(s) => format_sql_query::QuotedData(s).to_string(),
                 FacetOriginalValue::Int(i) => i.to_string(),
             }
         }).collect::<Vec<String>>().join(", ");
-        terms.push(format!(
-            "{field_name} IN ({values_str})",
-        ));
-    }
-
-    terms.join("
-        AND ")
-}
\ No newline at end of file
+        format!("{field_name} IN ({values_str})")
+    }).collect()
+}
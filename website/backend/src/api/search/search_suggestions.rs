@@ -0,0 +1,53 @@
+//! Query autocomplete / "did you mean" suggestions via Manticore's `CALL
+//! SUGGEST`.
+
+use common::search_suggestions::Suggestion;
+use serde::{Deserialize, Serialize};
+
+use crate::db_utils::manticore_utils::manticore_search_sql;
+
+/// Manticore index suggestions are looked up against — the same index
+/// `search_for_results`'s full-text queries match against.
+const SUGGEST_INDEX: &str = "doc_text_pages";
+
+/// Tokens shorter than this aren't worth suggesting against: too many
+/// equally-plausible candidate corrections to be useful.
+const MIN_SUGGEST_TOKEN_LEN: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SuggestRow {
+    suggest: String,
+    distance: u32,
+    docs: u64,
+    hits: u64,
+}
+
+/// Fuzzy-corrects the final whitespace-delimited token of `prefix` (leaving
+/// any already-typed tokens before it untouched) via `CALL SUGGEST`, ranked
+/// by ascending edit distance and then descending corpus `docs` count.
+/// Returns no suggestions when `prefix` has no final token, or that token is
+/// shorter than [`MIN_SUGGEST_TOKEN_LEN`].
+pub async fn search_suggestions(prefix: String, limit: u32) -> anyhow::Result<Vec<Suggestion>> {
+    let Some(last_token) = prefix.split_whitespace().last() else { return Ok(Vec::new()) };
+    if last_token.chars().count() < MIN_SUGGEST_TOKEN_LEN {
+        return Ok(Vec::new());
+    }
+
+    let sql = format!(
+        "CALL SUGGEST({}, {}, {{limit={limit}}})",
+        format_sql_query::QuotedData(last_token),
+        format_sql_query::QuotedData(SUGGEST_INDEX),
+    );
+    let response = manticore_search_sql::<SuggestRow>(sql).await?;
+
+    let mut suggestions = response.hits.hits.into_iter().map(|hit| Suggestion {
+        term: hit._source.suggest,
+        distance: hit._source.distance,
+        docs: hit._source.docs,
+        hits: hit._source.hits,
+    }).collect::<Vec<_>>();
+    suggestions.sort_by(|a, b| a.distance.cmp(&b.distance).then(b.docs.cmp(&a.docs)));
+    suggestions.truncate(limit as usize);
+
+    Ok(suggestions)
+}
@@ -0,0 +1,162 @@
+//! Unified multi-provider search used by the top-bar live preview: runs the
+//! query against document content, filenames and file-type metadata in
+//! parallel and returns each as its own grouped section, instead of forcing
+//! the user to pick a search mode up front.
+
+use common::{
+    search_query::SearchQuery,
+    search_result::SearchResultDocumentItem,
+    unified_search::{UnifiedSearchProvider, UnifiedSearchResultGroup, UnifiedSearchResults},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::search::{
+        search_sql::{build_facet_only_where_clause, build_field_scoped_where_clause, SQL_FROM_CLAUSE, SQL_OPTIONS_CLAUSE},
+        search_string_facet,
+    },
+    db_utils::{decompose_spans::decompose_text_into_spans, manticore_utils::manticore_search_sql},
+};
+
+/// Number of sample documents fetched per provider for the preview; the
+/// provider's `total_count` still reflects the full match count.
+pub const UNIFIED_PREVIEW_LIMIT: u64 = 5;
+
+pub async fn search_unified(query: SearchQuery) -> anyhow::Result<UnifiedSearchResults> {
+    if query.query_string.trim().is_empty() {
+        return Ok(UnifiedSearchResults { groups: vec![] });
+    }
+
+    let (content, filenames, metadata) = tokio::try_join!(
+        search_field_provider(&query, "page_text", UnifiedSearchProvider::Content),
+        search_field_provider(&query, "filenames", UnifiedSearchProvider::Filename),
+        search_metadata_provider(&query),
+    )?;
+
+    Ok(UnifiedSearchResults { groups: vec![content, filenames, metadata] })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct UnifiedFieldSearchResponse {
+    collection_dataset: String,
+    file_hash: String,
+    filenames: String,
+    highlight_text: String,
+    highlight_filenames: String,
+}
+
+async fn search_field_provider(
+    query: &SearchQuery,
+    field: &'static str,
+    provider: UnifiedSearchProvider,
+) -> anyhow::Result<UnifiedSearchResultGroup> {
+    let sql_where_clause = build_field_scoped_where_clause(query, field)?;
+    let sql = format!(
+        "
+        SELECT collection_dataset,
+            file_hash,
+            doc_metadata.filenames as filenames,
+            HIGHLIGHT({{
+                limit=200, limit_words=40, limit_snippets=1, html_strip_mode=strip,
+                before_match='<hoover4_strong>', after_match='</hoover4_strong>', around=30
+            }}, page_text) as highlight_text,
+            HIGHLIGHT({{
+                limit=200, limit_words=40, limit_snippets=1, html_strip_mode=strip,
+                before_match='<hoover4_strong>', after_match='</hoover4_strong>', around=30
+            }}, filenames) as highlight_filenames
+
+        {SQL_FROM_CLAUSE}
+        {sql_where_clause}
+
+        GROUP BY file_hash
+        LIMIT {UNIFIED_PREVIEW_LIMIT}
+
+        {SQL_OPTIONS_CLAUSE}
+        ;"
+    );
+    let response = manticore_search_sql::<UnifiedFieldSearchResponse>(sql).await?;
+    let total_count = response.hits.total;
+
+    let results = response.hits.hits.into_iter().enumerate().map(|(result_index_in_page, hit)| {
+        let title = hit._source.filenames.split("\n").next().unwrap_or("").to_string();
+        SearchResultDocumentItem {
+            collection_dataset: hit._source.collection_dataset,
+            file_hash: hit._source.file_hash,
+            title,
+            highlight_text_spans: decompose_text_into_spans(hit._source.highlight_text, &query.query_string, None),
+            highlight_filenames_spans: decompose_text_into_spans(hit._source.highlight_filenames, &query.query_string, None),
+            result_index_in_page: result_index_in_page as u64,
+            snippets_loaded: true,
+        }
+    }).collect();
+
+    Ok(UnifiedSearchResultGroup { provider, results, total_count })
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct UnifiedMetadataSearchResponse {
+    collection_dataset: String,
+    file_hash: String,
+    filenames: String,
+}
+
+/// "Metadata" matches here are documents whose file-type facet label (e.g.
+/// "pdf", "email") contains the query text: it's the only metadata field
+/// this schema exposes as a searchable display string today. The raw
+/// per-extractor metadata tables (see `get_raw_metadata`) are per-document
+/// debug dumps, not indexed for cross-document search, so they aren't a
+/// provider here.
+async fn search_metadata_provider(query: &SearchQuery) -> anyhow::Result<UnifiedSearchResultGroup> {
+    let mut facet_only_query = query.clone();
+    facet_only_query.query_string = String::new();
+    let matching_facets = search_string_facet(
+        facet_only_query,
+        "doc_metadata.file_types".to_string(),
+        Some("filetype".to_string()),
+        Some(query.query_string.clone()),
+        UNIFIED_PREVIEW_LIMIT,
+    ).await?;
+
+    if matching_facets.facet_values.is_empty() {
+        return Ok(UnifiedSearchResultGroup { provider: UnifiedSearchProvider::Metadata, results: vec![], total_count: 0 });
+    }
+
+    let total_count = matching_facets.facet_values.iter().map(|v| v.count).sum();
+
+    let mut documents_query = query.clone();
+    documents_query.query_string = String::new();
+    documents_query.facet_filters.insert(
+        "doc_metadata.file_types".to_string(),
+        matching_facets.facet_values.iter().map(|v| v.original_value.clone()).collect(),
+    );
+    let sql_where_clause = build_facet_only_where_clause(&documents_query);
+    let sql = format!(
+        "
+        SELECT collection_dataset,
+            file_hash,
+            doc_metadata.filenames as filenames
+        {SQL_FROM_CLAUSE}
+        {sql_where_clause}
+        GROUP BY file_hash
+        LIMIT {UNIFIED_PREVIEW_LIMIT}
+        {SQL_OPTIONS_CLAUSE}
+        ;"
+    );
+    let response = manticore_search_sql::<UnifiedMetadataSearchResponse>(sql).await?;
+
+    let results = response.hits.hits.into_iter().enumerate().map(|(result_index_in_page, hit)| {
+        let title = hit._source.filenames.split("\n").next().unwrap_or("").to_string();
+        let highlight_filenames_spans = decompose_text_into_spans(title.clone(), &query.query_string, None);
+        SearchResultDocumentItem {
+            collection_dataset: hit._source.collection_dataset,
+            file_hash: hit._source.file_hash,
+            title,
+            highlight_text_spans: decompose_text_into_spans(String::new(), &query.query_string, None),
+            highlight_filenames_spans,
+            result_index_in_page: result_index_in_page as u64,
+            snippets_loaded: true,
+        }
+    }).collect();
+
+    Ok(UnifiedSearchResultGroup { provider: UnifiedSearchProvider::Metadata, results, total_count })
+}
@@ -2,12 +2,80 @@
 
 use common::text_highlight::HighlightTextSpan;
 
+use crate::db_utils::query_parser::{parse_query, QueryClause};
+use crate::db_utils::token_match::{normalize_text, tokenize, tokens_match};
+
 const START_TAG: &str = "<hoover4_strong>";
 const END_TAG: &str = "</hoover4_strong>";
 
-pub fn decompose_text_into_spans(text: String) -> Vec<HighlightTextSpan> {
+/// A distinct search term a highlighted span can be attributed to: a single
+/// word, or the words of a quoted phrase kept together.
+struct HighlightTerm {
+    tokens: Vec<String>,
+    display_text: String,
+}
+
+/// Tokenizes `query_string` into its distinct highlightable terms: quoted
+/// phrases stay grouped as one term, `-exclusion` words are dropped (they
+/// can't appear in a highlighted span by definition), and duplicate terms
+/// are collapsed so `term_index` stays meaningful.
+fn highlight_terms(query_string: &str) -> Vec<HighlightTerm> {
+    let mut terms: Vec<HighlightTerm> = Vec::new();
+    for clause in parse_query(query_string).clauses {
+        let tokens = match clause {
+            QueryClause::Term(token) => vec![token],
+            QueryClause::Phrase(tokens) => tokens,
+            QueryClause::Not(_) => continue,
+        };
+        if tokens.is_empty() || terms.iter().any(|t| t.tokens == tokens) {
+            continue;
+        }
+        let display_text = tokens.join(" ");
+        terms.push(HighlightTerm { tokens, display_text });
+    }
+    terms
+}
+
+/// Matches `span_text` against `terms`, picking the longest (most specific)
+/// term whose tokens occur as a contiguous run within the span's tokens.
+/// Manticore highlights stemmed forms, so token comparison goes through the
+/// same typo-tolerant matcher used for hit counting.
+fn match_highlight_term(span_text: &str, terms: &[HighlightTerm]) -> (Option<usize>, Option<String>) {
+    let span_tokens = tokenize(&normalize_text(span_text));
+    if span_tokens.is_empty() {
+        return (None, None);
+    }
+
+    let mut best: Option<usize> = None;
+    for (term_index, term) in terms.iter().enumerate() {
+        if term.tokens.is_empty() || term.tokens.len() > span_tokens.len() {
+            continue;
+        }
+        let term_matches = (0..=span_tokens.len() - term.tokens.len()).any(|start| {
+            term.tokens.iter().zip(&span_tokens[start..]).all(|(term_token, span_token)| tokens_match(term_token, span_token))
+        });
+        if !term_matches {
+            continue;
+        }
+        let is_more_specific = best.map_or(true, |best_index| term.tokens.len() > terms[best_index].tokens.len());
+        if is_more_specific {
+            best = Some(term_index);
+        }
+    }
+
+    match best {
+        Some(term_index) => (Some(term_index), Some(terms[term_index].display_text.clone())),
+        None => (None, None),
+    }
+}
 
-    let mut v = _do_decompose_text_into_spans(text);
+/// `page_id` is stamped onto every span the caller can attribute to a single
+/// known page (e.g. a per-page document search), and left `None` when the
+/// text was drawn from a cross-page aggregate (e.g. the per-document grouped
+/// highlight used by the unified search preview, or a filenames highlight).
+pub fn decompose_text_into_spans(text: String, query_string: &str, page_id: Option<u32>) -> Vec<HighlightTextSpan> {
+    let terms = highlight_terms(query_string);
+    let mut v = _do_decompose_text_into_spans(text, &terms, page_id);
     let mut index = 0;
     for item in v.iter_mut() {
         if item.is_highlighted {
@@ -18,7 +86,7 @@ pub fn decompose_text_into_spans(text: String) -> Vec<HighlightTextSpan> {
     v
 }
 
-fn _do_decompose_text_into_spans(text: String) -> Vec<HighlightTextSpan> {
+fn _do_decompose_text_into_spans(text: String, terms: &[HighlightTerm], page_id: Option<u32>) -> Vec<HighlightTextSpan> {
     let text = text.replace("���", "�");
     let text = text.replace("��", "�");
     // let text = text.replace("\n", " ");
@@ -30,7 +98,7 @@ fn _do_decompose_text_into_spans(text: String) -> Vec<HighlightTextSpan> {
     // Fast-path: if there is no opening <strong>, we don't attempt to parse.
     // Return a single non-highlighted span with the original text (preserving any stray closers).
     if !text.contains(START_TAG) {
-        return vec![HighlightTextSpan { text, is_highlighted: false, index: 0 }];
+        return vec![HighlightTextSpan { text, is_highlighted: false, index: 0, term_index: None, term_text: None, page_id }];
     }
 
     let input = text;
@@ -40,14 +108,16 @@ fn _do_decompose_text_into_spans(text: String) -> Vec<HighlightTextSpan> {
     let mut i: usize = 0;
     let s = input.as_str();
 
-    // Helper to flush the current buffer into a span, merging with the previous span
-    // if it shares the same highlight state to avoid tiny adjacent spans.
+    // Flushes the current buffer into a span, merging with the previous span
+    // when it shares the same highlight state (and, for highlighted spans,
+    // the same matched term) to avoid tiny adjacent spans of one query term.
     let flush_buffer = |spans: &mut Vec<HighlightTextSpan>, buffer: &mut String, highlighted: bool| {
         if buffer.is_empty() {
             return;
         }
+        let (term_index, term_text) = if highlighted { match_highlight_term(buffer, terms) } else { (None, None) };
         if let Some(last) = spans.last_mut() {
-            if last.is_highlighted == highlighted {
+            if last.is_highlighted == highlighted && (!highlighted || last.term_index == term_index) {
                 last.text.push_str(buffer);
                 buffer.clear();
                 return;
@@ -57,6 +127,9 @@ fn _do_decompose_text_into_spans(text: String) -> Vec<HighlightTextSpan> {
             text: std::mem::take(buffer),
             is_highlighted: highlighted,
             index: 0,
+            term_index,
+            term_text,
+            page_id,
         });
     };
 
@@ -110,4 +183,134 @@ fn _do_decompose_text_into_spans(text: String) -> Vec<HighlightTextSpan> {
     flush_buffer(&mut spans, &mut buffer, strong_depth > 0);
 
     spans
-}
\ No newline at end of file
+}
+
+/// A single word-granularity token, carrying whichever highlight span it
+/// came from (if any), used as the intermediate representation for cropping.
+struct WordToken {
+    text: String,
+    is_highlighted: bool,
+    hit_index: u64,
+    term_index: Option<usize>,
+    term_text: Option<String>,
+    page_id: Option<u32>,
+}
+
+/// Splits `text` into word tokens, each keeping its trailing whitespace so
+/// the tokens can be concatenated back into the original text verbatim.
+fn split_into_word_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        current.push(c);
+        if c.is_whitespace() {
+            while let Some(&next) = chars.peek() {
+                if !next.is_whitespace() {
+                    break;
+                }
+                current.push(next);
+                chars.next();
+            }
+            tokens.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn flatten_to_word_tokens(spans: &[HighlightTextSpan]) -> Vec<WordToken> {
+    let mut out = Vec::new();
+    for span in spans {
+        for word in split_into_word_tokens(&span.text) {
+            out.push(WordToken {
+                text: word,
+                is_highlighted: span.is_highlighted,
+                hit_index: span.index,
+                term_index: span.term_index,
+                term_text: span.term_text.clone(),
+                page_id: span.page_id,
+            });
+        }
+    }
+    out
+}
+
+/// Crops a decomposed span sequence down to bounded windows of
+/// `crop_radius` words before/after each highlighted hit, inserting a "…"
+/// marker span at every truncation edge. Overlapping windows from adjacent
+/// hits are merged into one contiguous span sequence. The `index` field on
+/// highlighted spans is preserved so word-stepping navigation still lines
+/// up with the uncropped hit count.
+pub fn crop_spans_around_hits(spans: Vec<HighlightTextSpan>, crop_radius: usize) -> Vec<HighlightTextSpan> {
+    if crop_radius == 0 {
+        return spans;
+    }
+    let tokens = flatten_to_word_tokens(&spans);
+    if tokens.is_empty() {
+        return spans;
+    }
+
+    // Group consecutive highlighted tokens that share a hit index into one run.
+    let mut hit_ranges: Vec<(usize, usize)> = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i].is_highlighted {
+            let start = i;
+            let hit_index = tokens[i].hit_index;
+            let mut end = i;
+            while end + 1 < tokens.len() && tokens[end + 1].is_highlighted && tokens[end + 1].hit_index == hit_index {
+                end += 1;
+            }
+            hit_ranges.push((start, end));
+            i = end + 1;
+        } else {
+            i += 1;
+        }
+    }
+    if hit_ranges.is_empty() {
+        return spans;
+    }
+
+    let mut windows: Vec<(usize, usize)> = hit_ranges
+        .iter()
+        .map(|(start, end)| (start.saturating_sub(crop_radius), (end + crop_radius).min(tokens.len() - 1)))
+        .collect();
+    windows.sort();
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in windows.drain(..) {
+        if let Some(last) = merged.last_mut() {
+            if start <= last.1 + 1 {
+                last.1 = last.1.max(end);
+                continue;
+            }
+        }
+        merged.push((start, end));
+    }
+
+    let mut result: Vec<HighlightTextSpan> = Vec::new();
+    for (start, end) in merged {
+        if start > 0 {
+            push_cropped_span(&mut result, "… ".to_string(), false, 0, None, None, None);
+        }
+        for token in &tokens[start..=end] {
+            push_cropped_span(&mut result, token.text.clone(), token.is_highlighted, token.hit_index, token.term_index, token.term_text.clone(), token.page_id);
+        }
+        if end < tokens.len() - 1 {
+            push_cropped_span(&mut result, " …".to_string(), false, 0, None, None, None);
+        }
+    }
+    result
+}
+
+fn push_cropped_span(spans: &mut Vec<HighlightTextSpan>, text: String, is_highlighted: bool, index: u64, term_index: Option<usize>, term_text: Option<String>, page_id: Option<u32>) {
+    if let Some(last) = spans.last_mut() {
+        if last.is_highlighted == is_highlighted && (!is_highlighted || (last.index == index && last.term_index == term_index)) {
+            last.text.push_str(&text);
+            return;
+        }
+    }
+    spans.push(HighlightTextSpan { text, is_highlighted, index, term_index, term_text, page_id });
+}
@@ -1,13 +1,35 @@
+use clickhouse::Row;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::collections::BTreeMap;
 use crate::db_utils::clickhouse_utils::get_clickhouse_client;
 
+/// How long a `search_manticore_cache` row stays eligible to serve a
+/// lookup before it's treated as a miss, so a reindex or a document
+/// removed from a collection can't keep being masked by a stale hit
+/// forever. Defaults to 5 minutes; override with `MANTICORE_CACHE_TTL_SECONDS`.
+fn cache_ttl_seconds() -> u32 {
+    std::env::var("MANTICORE_CACHE_TTL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(300)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RawSarchResult<T> {
     pub hits: RawSearchResultHits<T>,
     pub timed_out: bool,
     pub took: u64,
     pub aggregations: Option<BTreeMap<String, RawSearchResultAggregation>>,
+    /// True when this result was served from `search_manticore_cache`
+    /// instead of a fresh Manticore query. Not part of the cached JSON
+    /// payload itself; filled in by `manticore_search_sql` after the fact.
+    #[serde(default, skip_serializing)]
+    pub cache_hit: bool,
+    /// Wall-clock duration of the underlying Manticore search, in
+    /// milliseconds. On a cache hit this is the original query's duration,
+    /// not the (near-zero) time it took to read the cache row.
+    #[serde(default, skip_serializing)]
+    pub duration_ms: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -41,9 +63,11 @@ pub async fn manticore_search_sql<T: DeserializeOwned + std::fmt::Debug>(
     sql: String,
 ) -> anyhow::Result<RawSarchResult<T>> {
     let query_hash = sha256::digest(sql.clone());
-    if let Ok(cached_response) = get_cached_response(&query_hash, &sql).await {
-        if let Ok(response) = serde_json::from_str::<RawSarchResult<T>>(&cached_response) {
+    if let Ok((cached_response, cached_duration_ms)) = get_cached_response(&query_hash, &sql).await {
+        if let Ok(mut response) = serde_json::from_str::<RawSarchResult<T>>(&cached_response) {
             println!("SEARCH CACHE HIT: {}", query_hash);
+            response.cache_hit = true;
+            response.duration_ms = cached_duration_ms;
             return Ok(response);
         }
     }
@@ -62,25 +86,37 @@ pub async fn manticore_search_sql<T: DeserializeOwned + std::fmt::Debug>(
     println!("SEARCH RESPONSE: len = {}", response_txt.len());
     let t1 = std::time::Instant::now();
     let dt_ms = t1.duration_since(t0).as_millis() as u32;
-    if insert_cache(&query_hash, &sql, &response_txt, dt_ms).await.is_ok() {
+    let mut response: RawSarchResult<T> = serde_json::from_str(&response_txt)?;
+    // A timed-out response only ever reflects whatever Manticore managed to
+    // gather before the deadline, so it must never become a sticky cache
+    // entry that later queries reuse in place of the real, complete result.
+    if response.timed_out {
+        println!("SEARCH CACHE SKIPPED (timed out): {}", query_hash);
+    } else if insert_cache(&query_hash, &sql, &response_txt, dt_ms).await.is_ok() {
         println!("SEARCH CACHE INSERTED: {} (searched in {}ms)", query_hash, dt_ms);
     } else {
         println!("SEARCH CACHE INSERT FAILED: {}", query_hash);
     }
-    // CACHE THE RESPONSE TEXT
-    let response: RawSarchResult<T> = serde_json::from_str(&response_txt)?;
+    response.cache_hit = false;
+    response.duration_ms = dt_ms;
     Ok(response)
 }
 
+#[derive(Debug, Row, Deserialize)]
+struct CachedResultRow {
+    result_json: String,
+    duration_ms: u32,
+}
 
-async fn get_cached_response(query_hash: &String, query_string: &String) -> anyhow::Result<String> {
+async fn get_cached_response(query_hash: &String, query_string: &String) -> anyhow::Result<(String, u32)> {
 
     let client = get_clickhouse_client();
     let sql = "
-    SELECT result_json
+    SELECT result_json, duration_ms
     FROM search_manticore_cache
     WHERE query_hash = ?
       AND query_string = ?
+      AND date_created >= now() - ?
     ORDER BY date_created DESC
     LIMIT 1
     ";
@@ -88,10 +124,11 @@ async fn get_cached_response(query_hash: &String, query_string: &String) -> anyh
         .query(sql)
         .bind(query_hash.clone())
         .bind(query_string.clone())
-        .fetch_all::<String>()
+        .bind(cache_ttl_seconds())
+        .fetch_all::<CachedResultRow>()
         .await?;
-    if let Some(result_json) = rows.into_iter().next() {
-        Ok(result_json)
+    if let Some(row) = rows.into_iter().next() {
+        Ok((row.result_json, row.duration_ms))
     } else {
         anyhow::bail!("Cache miss")
     }
@@ -113,4 +150,29 @@ async fn insert_cache(query_hash: &String, query_string: &String, response_txt:
         .execute()
         .await?;
     Ok(())
+}
+
+/// Deletes rows from `search_manticore_cache` matching `query_substring`
+/// (a `LIKE %substring%` match against the cached `query_string`),
+/// `older_than_seconds` (rows whose `date_created` falls further back than
+/// that many seconds), or both. Requires at least one filter so a call
+/// can't accidentally wipe the whole cache.
+pub async fn purge_cache(query_substring: Option<String>, older_than_seconds: Option<u32>) -> anyhow::Result<()> {
+    if query_substring.is_none() && older_than_seconds.is_none() {
+        anyhow::bail!("purge_cache requires at least one of query_substring or older_than_seconds");
+    }
+
+    let mut conditions = Vec::new();
+    if let Some(substring) = &query_substring {
+        conditions.push(format!("query_string LIKE {}", format_sql_query::QuotedData(&format!("%{}%", substring))));
+    }
+    if let Some(seconds) = older_than_seconds {
+        conditions.push(format!("date_created < now() - {seconds}"));
+    }
+    let where_clause = conditions.join(" AND ");
+
+    let client = get_clickhouse_client();
+    let sql = format!("ALTER TABLE search_manticore_cache DELETE WHERE {where_clause}");
+    client.query(&sql).execute().await?;
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,156 @@
+//! Parses `DocViewerState.find_query` into a structured query so hit
+//! counting/highlighting can support quoted phrases and `-term` exclusions
+//! instead of treating the raw string as one opaque term.
+
+use crate::db_utils::token_match::{normalize_text, tokenize, tokens_match};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryClause {
+    /// A sequence of tokens that must appear consecutively.
+    Phrase(Vec<String>),
+    /// A single required token (implicit AND with every other clause).
+    Term(String),
+    /// A single token that must NOT appear anywhere on the page.
+    Not(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParsedQuery {
+    pub clauses: Vec<QueryClause>,
+}
+
+/// Parses a raw find-query string into double-quoted phrases, `-term`
+/// exclusions, and implicit-AND terms. Normalization (lowercasing/diacritic
+/// stripping) matches [`crate::db_utils::token_match`] so clauses compare
+/// directly against page tokens.
+pub fn parse_query(raw: &str) -> ParsedQuery {
+    let mut clauses = Vec::new();
+    let chars: Vec<char> = raw.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if chars[i] == '"' {
+            let start = i + 1;
+            let mut end = start;
+            while end < chars.len() && chars[end] != '"' {
+                end += 1;
+            }
+            let phrase_text: String = chars[start..end].iter().collect();
+            let phrase_tokens = tokenize(&normalize_text(&phrase_text));
+            if !phrase_tokens.is_empty() {
+                clauses.push(QueryClause::Phrase(phrase_tokens));
+            }
+            i = if end < chars.len() { end + 1 } else { end };
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() && !chars[i].is_whitespace() && chars[i] != '"' {
+            i += 1;
+        }
+        let word: String = chars[start..i].iter().collect();
+        if let Some(negated) = word.strip_prefix('-') {
+            let tokens = tokenize(&normalize_text(negated));
+            if let Some(token) = tokens.into_iter().next() {
+                clauses.push(QueryClause::Not(token));
+            }
+        } else {
+            let tokens = tokenize(&normalize_text(&word));
+            for token in tokens {
+                clauses.push(QueryClause::Term(token));
+            }
+        }
+    }
+
+    ParsedQuery { clauses }
+}
+
+/// Returns true when `page_tokens` satisfies every positive clause and no
+/// negative clause of `parsed`.
+pub fn page_matches(parsed: &ParsedQuery, page_tokens: &[String]) -> bool {
+    for clause in &parsed.clauses {
+        if let QueryClause::Not(term) = clause {
+            if page_tokens.iter().any(|t| tokens_match(term, t)) {
+                return false;
+            }
+        }
+    }
+    for clause in &parsed.clauses {
+        match clause {
+            QueryClause::Term(term) => {
+                if !page_tokens.iter().any(|t| tokens_match(term, t)) {
+                    return false;
+                }
+            }
+            QueryClause::Phrase(words) => {
+                if !phrase_occurs(words, page_tokens) {
+                    return false;
+                }
+            }
+            QueryClause::Not(_) => {}
+        }
+    }
+    true
+}
+
+fn phrase_occurs(words: &[String], page_tokens: &[String]) -> bool {
+    if words.is_empty() || words.len() > page_tokens.len() {
+        return words.is_empty();
+    }
+    'windows: for start in 0..=(page_tokens.len() - words.len()) {
+        for (offset, word) in words.iter().enumerate() {
+            if !tokens_match(word, &page_tokens[start + offset]) {
+                continue 'windows;
+            }
+        }
+        return true;
+    }
+    false
+}
+
+/// Counts the sliding-window positions in `page_text` where every positive
+/// clause of `parsed` is satisfied within a proximity window, returning 0
+/// outright if any negative clause matches anywhere on the page.
+pub fn count_parsed_query_hits(page_text: &str, parsed: &ParsedQuery, proximity_slack: usize) -> u32 {
+    let page_tokens = tokenize(&normalize_text(page_text));
+    if page_tokens.is_empty() {
+        return 0;
+    }
+    if !page_matches(parsed, &page_tokens) {
+        return 0;
+    }
+
+    let positive_token_count: usize = parsed.clauses.iter().map(|c| match c {
+        QueryClause::Term(_) => 1,
+        QueryClause::Phrase(words) => words.len(),
+        QueryClause::Not(_) => 0,
+    }).sum();
+    if positive_token_count == 0 {
+        return 0;
+    }
+
+    let window = positive_token_count + proximity_slack;
+    let mut count = 0u32;
+    let mut start = 0;
+    while start < page_tokens.len() {
+        let end = (start + window).min(page_tokens.len());
+        let window_tokens = &page_tokens[start..end];
+        let all_match = parsed.clauses.iter().all(|clause| match clause {
+            QueryClause::Term(term) => window_tokens.iter().any(|pt| tokens_match(term, pt)),
+            QueryClause::Phrase(words) => phrase_occurs(words, window_tokens),
+            QueryClause::Not(_) => true,
+        });
+        if all_match {
+            count += 1;
+            // Advance past this whole window so a single occurrence isn't
+            // re-counted once per overlapping start index.
+            start += window;
+        } else {
+            start += 1;
+        }
+    }
+    count
+}
@@ -0,0 +1,148 @@
+//! Shared typo-tolerant, word-boundary-aware token matching.
+//!
+//! Used anywhere we need to count how many times a query matches inside a
+//! blob of extracted text without relying on naive substring search, which
+//! both over-counts matches inside larger words ("cat" inside "category")
+//! and is brittle to OCR/typo noise.
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Default sliding-window slack (in tokens) allowed between the first and
+/// last matched query token of a multi-term query.
+pub const DEFAULT_PROXIMITY_SLACK: usize = 2;
+
+/// Normalizes text for matching: lowercases and strips diacritics (NFD
+/// decomposition followed by dropping combining marks).
+pub fn normalize_text(text: &str) -> String {
+    text.nfd().filter(|c| !is_combining_mark(*c)).collect::<String>().to_lowercase()
+}
+
+fn is_combining_mark(c: char) -> bool {
+    matches!(c as u32, 0x0300..=0x036F)
+}
+
+/// Splits normalized text into word tokens on Unicode word boundaries,
+/// discarding boundaries that are pure whitespace/punctuation.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.unicode_words().map(|w| w.to_string()).collect()
+}
+
+/// Mirrors common search-engine defaults: the typo budget grows with the
+/// token length, since short tokens are much more likely to be genuinely
+/// different words after even a single edit.
+pub fn typo_budget_for_len(token_len: usize) -> usize {
+    match token_len {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Damerau-Levenshtein edit distance with an early-exit band: once the
+/// running minimum of a row exceeds `max_distance`, the computation aborts
+/// and returns `None` rather than finishing the full DP table.
+pub fn bounded_damerau_levenshtein(a: &str, b: &str, max_distance: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.len().abs_diff(b.len()) > max_distance {
+        return None;
+    }
+
+    let mut prev_prev = vec![0usize; b.len() + 1];
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        let mut row_min = curr[0];
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut best = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(prev_prev[j - 2] + 1);
+            }
+            curr[j] = best;
+            row_min = row_min.min(best);
+        }
+        if row_min > max_distance {
+            return None;
+        }
+        std::mem::swap(&mut prev_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    let distance = prev[b.len()];
+    if distance > max_distance { None } else { Some(distance) }
+}
+
+/// Returns true if `page_token` matches `query_token` within the
+/// length-based typo budget.
+pub fn tokens_match(query_token: &str, page_token: &str) -> bool {
+    let budget = typo_budget_for_len(query_token.chars().count());
+    bounded_damerau_levenshtein(query_token, page_token, budget).is_some()
+}
+
+/// Counts how many times a (possibly multi-term) query matches inside
+/// `page_text`, tolerating typos per [`tokens_match`] and requiring all
+/// query tokens to appear within a sliding window of consecutive page
+/// tokens (`query_len + proximity_slack`).
+pub fn count_token_hits(page_text: &str, query: &str, proximity_slack: usize) -> u32 {
+    let page_tokens = tokenize(&normalize_text(page_text));
+    let query_tokens = tokenize(&normalize_text(query));
+    if query_tokens.is_empty() || page_tokens.is_empty() {
+        return 0;
+    }
+    if query_tokens.len() == 1 {
+        return page_tokens.iter().filter(|t| tokens_match(&query_tokens[0], t)).count() as u32;
+    }
+
+    let window = query_tokens.len() + proximity_slack;
+    let mut count = 0u32;
+    let mut start = 0;
+    while start < page_tokens.len() {
+        let end = (start + window).min(page_tokens.len());
+        let window_tokens = &page_tokens[start..end];
+        let all_match = query_tokens.iter().all(|qt| window_tokens.iter().any(|pt| tokens_match(qt, pt)));
+        if all_match {
+            count += 1;
+            // Advance past this whole window so a single occurrence isn't
+            // re-counted once per overlapping start index.
+            start += window;
+        } else {
+            start += 1;
+        }
+    }
+    count
+}
+
+/// Exact (non-typo-tolerant) variant for callers that opt out of fuzziness.
+pub fn count_token_hits_exact(page_text: &str, query: &str, proximity_slack: usize) -> u32 {
+    let page_tokens = tokenize(&normalize_text(page_text));
+    let query_tokens = tokenize(&normalize_text(query));
+    if query_tokens.is_empty() || page_tokens.is_empty() {
+        return 0;
+    }
+    if query_tokens.len() == 1 {
+        return page_tokens.iter().filter(|t| **t == query_tokens[0]).count() as u32;
+    }
+
+    let window = query_tokens.len() + proximity_slack;
+    let mut count = 0u32;
+    let mut start = 0;
+    while start < page_tokens.len() {
+        let end = (start + window).min(page_tokens.len());
+        let window_tokens = &page_tokens[start..end];
+        let all_match = query_tokens.iter().all(|qt| window_tokens.iter().any(|pt| pt == qt));
+        if all_match {
+            count += 1;
+            // Advance past this whole window so a single occurrence isn't
+            // re-counted once per overlapping start index.
+            start += window;
+        } else {
+            start += 1;
+        }
+    }
+    count
+}
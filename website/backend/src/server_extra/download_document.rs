@@ -1,67 +1,89 @@
-use anyhow::Context;
-use axum::{body::Body, extract::Path, response::{IntoResponse, Response}};
+use axum::{body::Body, extract::{HeaderMap, Path}, http::StatusCode, response::{IntoResponse, Response}};
 use common::search_result::DocumentIdentifier;
-use minio::s3::{creds::StaticProvider, http::BaseUrl, types::S3Api};
-use minio::s3::Client;
-use reqwest::StatusCode;
 use tracing::info;
 
-use crate::api::documents::download_document::{get_blob_filename, get_document_blob_content_from_clickhouse, get_document_s3_blob_download_path};
+use crate::api::documents::download_document::{get_blob_filename, get_document_content_stream, Range};
 
-async fn _download_document(Path((collection_dataset, file_hash)): Path<(String, String)>) -> anyhow::Result<impl IntoResponse> {
-    info!("Downloading document: {}/{}", collection_dataset, file_hash);
+/// `filename` comes straight from `vfs_files.path` — investigated,
+/// adversary-controlled document data — so it can carry raw control bytes
+/// (`\r`/`\n`) or quote/backslash characters that would otherwise either
+/// break out of the quoted `Content-Disposition` filename or make
+/// `HeaderValue::from_str` reject the header outright. Strip/replace those
+/// before it ever reaches the header builder.
+fn sanitize_disposition_filename(filename: &str) -> String {
+    filename.chars().map(|c| if c.is_control() || c == '"' || c == '\\' { '_' } else { c }).collect()
+}
+
+/// Parses a single-range `Range: bytes=start-end` / `bytes=start-` header
+/// value. Suffix ranges (`bytes=-500`) and multi-range requests aren't
+/// supported by `get_document_content_stream`, so they're treated as "no
+/// range" and the full document is returned instead of rejecting the request.
+fn parse_range_header(header_value: &str) -> Option<Range> {
+    let spec = header_value.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+    let start: u64 = start.parse().ok()?;
+    let end = if end.is_empty() { None } else { Some(end.parse().ok()?) };
+    Some(Range { start, end })
+}
+
+async fn _download_document(document_identifier: DocumentIdentifier, range: Option<Range>) -> anyhow::Result<Response> {
+    info!("Downloading document: {}/{}", document_identifier.collection_dataset, document_identifier.file_hash);
 
-    let document_identifier = DocumentIdentifier {
-        collection_dataset,
-        file_hash,
-    };
     let filename = get_blob_filename(document_identifier.clone()).await?;
-    let headers: [(String, String); 2] = [
-        ("Content-Type".to_string(), "application/octet-stream".to_string()),
-        (
-           "Content-Disposition".to_string(),
-            format!("attachment; filename=\"{}\"", filename),
-        ),
-    ];
+    let content_type = mime_guess::from_path(&filename).first_or_octet_stream();
+
+    let (_stream_size, satisfied_range, stream) = get_document_content_stream(document_identifier, range).await?;
+    let body = Body::from_stream(stream);
 
-    let blob_info = get_document_s3_blob_download_path(document_identifier.clone()).await?;
-    let blob_size = blob_info.blob_size_bytes;
-    tracing::info!("Blob size: {}", blob_size);
-    tracing::info!("Blob info: {:#?}", blob_info);
-    if blob_info.stored_in_clickhouse {
-        tracing::info!("Downloading document from clickhouse");
-        let blob_value = get_document_blob_content_from_clickhouse(document_identifier.clone()).await?;
-        let data = blob_value.blob_value;
-        assert_eq!(data.len(), blob_size as usize);
-        let body = Body::from(data);
+    let disposition = format!("attachment; filename=\"{}\"", sanitize_disposition_filename(&filename));
+    let mut response = match satisfied_range {
+        Some(range) => {
+            let mut response = (
+                StatusCode::PARTIAL_CONTENT,
+                [
+                    ("Content-Range", format!("bytes {}-{}/{}", range.start, range.end, range.total_size)),
+                    ("Content-Length", format!("{}", range.end - range.start + 1)),
+                ],
+                body,
+            ).into_response();
+            response.headers_mut().insert("Accept-Ranges", "bytes".parse().unwrap());
+            response
+        }
+        None => {
+            let mut response = (StatusCode::OK, body).into_response();
+            response.headers_mut().insert("Accept-Ranges", "bytes".parse().unwrap());
+            response
+        }
+    };
 
-        return Ok((headers, body).into_response())
-    } else {
-        tracing::info!("Downloading document from s3");
-        let s3_path = blob_info.s3_path.replace("s3://hoover4-blobs/", "");
-        tracing::info!("S3 path: {}", s3_path);
-        let s3_bucket = "hoover4-blobs";
-        let s3_endpoint = std::env::var("S3_ENDPOINT").context("S3_ENDPOINT is not set")?;
-        let base_url = s3_endpoint.parse::<BaseUrl>().context("Failed to parse s3 endpoint")?;
-        let static_provider = StaticProvider::new("hoover4", "hoover4-secret", None);
-        let client = Client::new(base_url, Some(Box::new(static_provider)), None, None).context("Failed to create s3 client")?;
-        let object = client.get_object(s3_bucket, s3_path).send().await.context("Failed to get object")?;
-        let object_size = object.object_size as usize;
-        assert_eq!(object_size, blob_size as usize);
-        let (stream, _size) = object.content.to_stream().await.context("Failed to get object stream")?;
+    let headers = response.headers_mut();
+    headers.insert("Content-Type", content_type.essence_str().parse().unwrap());
+    headers.insert(
+        "Content-Disposition",
+        disposition.parse().unwrap_or_else(|_| axum::http::HeaderValue::from_static("attachment")),
+    );
 
-        // let stream = client.get_object(s3_bucket, s3_path).await?.bytes_stream();
-        let body = Body::from_stream(stream);
-        return Ok((headers, body).into_response())
-    }
+    Ok(response)
 }
 
-pub async fn download_document(Path((collection_dataset, file_hash)): Path<(String, String)>) ->   Response {
-    match _download_document(Path((collection_dataset, file_hash))).await {
-        Ok(response) => response.into_response(),
+pub async fn download_document(Path((collection_dataset, file_hash)): Path<(String, String)>, headers: HeaderMap) -> Response {
+    let document_identifier = DocumentIdentifier { collection_dataset, file_hash };
+    let range = headers.get(axum::http::header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_range_header);
+
+    match _download_document(document_identifier, range).await {
+        Ok(response) => response,
         Err(e) => {
+            let message = e.to_string();
             tracing::error!("download_document: request failed: {:#?}", e);
-            return (StatusCode::INTERNAL_SERVER_ERROR, Body::from(e.to_string())).into_response();
+            if message.contains("not found") {
+                (StatusCode::NOT_FOUND, Body::from(message)).into_response()
+            } else if message.contains("not satisfiable") {
+                (StatusCode::RANGE_NOT_SATISFIABLE, Body::from(message)).into_response()
+            } else {
+                (StatusCode::INTERNAL_SERVER_ERROR, Body::from(message)).into_response()
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,67 @@
+//! Shared types for the server-side syntax-highlighted source-code viewer.
+
+use serde::{Deserialize, Serialize};
+
+/// The three ways `DocumentPreviewForSearch` knows how to render a
+/// document, picked by `get_document_type` ahead of fetching any page
+/// content so the viewer can dispatch to the right component up front.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DocumentType {
+    Pdf,
+    Code,
+    Text,
+}
+
+/// Selectable color scheme for [`CodeDataViewer`], persisted on
+/// `DocViewerState` so it survives navigation. Purely a client-side display
+/// concern — the backend's token classes are colored by whichever theme is
+/// active, not baked in server-side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CodeHighlightTheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl CodeHighlightTheme {
+    pub fn toggled(self) -> Self {
+        match self {
+            CodeHighlightTheme::Light => CodeHighlightTheme::Dark,
+            CodeHighlightTheme::Dark => CodeHighlightTheme::Light,
+        }
+    }
+}
+
+/// Mirrors `SyntaxTokenClass` in the frontend's snippet lexer, but this one
+/// is classified server-side over a whole file rather than a single cropped
+/// snippet fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CodeTokenClass {
+    Plain,
+    Comment,
+    String,
+    Number,
+    Keyword,
+}
+
+/// A contiguous run of source text sharing one [`CodeTokenClass`], rendered
+/// as a plain Dioxus text node (never raw HTML) the same way
+/// `HighlightTextSpan` is, so there's no escape-then-reinsert step to get
+/// wrong.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeToken {
+    pub text: String,
+    pub class: CodeTokenClass,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeHighlightLine {
+    pub line_number: u32,
+    pub tokens: Vec<CodeToken>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeHighlightResponse {
+    pub language: String,
+    pub lines: Vec<CodeHighlightLine>,
+}
@@ -0,0 +1,14 @@
+//! Shared types for the collection/folder tree explorer.
+
+use serde::{Deserialize, Serialize};
+
+/// One entry in a directory listing: either a sub-directory (no hash/size)
+/// or a leaf file, identified by the `vfs_files` path it was derived from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileBrowserEntry {
+    pub name: String,
+    pub path: String,
+    pub is_directory: bool,
+    pub file_hash: Option<String>,
+    pub size_bytes: Option<u64>,
+}
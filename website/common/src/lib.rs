@@ -9,4 +9,10 @@ pub mod text_highlight;
 pub mod search_const;
 pub mod document_metadata;
 pub mod document_text_sources;
-pub mod pdf_to_html_conversion;
\ No newline at end of file
+pub mod pdf_to_html_conversion;
+pub mod unified_search;
+pub mod file_browser;
+pub mod code_highlight;
+pub mod search_suggestions;
+pub mod search_export;
+pub mod saved_search;
\ No newline at end of file
@@ -8,4 +8,24 @@ pub struct PDFToHtmlConversionResponse {
     pub styles: Vec<String>,
     pub page_width_px: f32,
     pub page_height_px: f32,
+    /// True when at least one page contains a `$...$`/`$$...$$` math span,
+    /// so the viewer knows to run the KaTeX render pass. Documents with no
+    /// math skip it entirely rather than paying for an unused script.
+    pub has_math: bool,
+    pub page_count: u32,
+}
+
+/// A window of already-converted pages starting at `start_page`, returned
+/// instead of the full [`PDFToHtmlConversionResponse`] so
+/// `DocumentPreviewForPdf`'s continuous-scroll viewer doesn't ship every
+/// page of a long document up front.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PDFPageRangeResponse {
+    pub start_page: u32,
+    pub pages: Vec<String>,
+    pub styles: Vec<String>,
+    pub page_width_px: f32,
+    pub page_height_px: f32,
+    pub has_math: bool,
+    pub page_count: u32,
 }
\ No newline at end of file
@@ -0,0 +1,20 @@
+//! Shared types for named, re-runnable saved searches.
+
+use serde::{Deserialize, Serialize};
+
+/// A user-named, persisted search. `encoded_query` is opaque here: it's the
+/// frontend's own `UrlParam<SearchQuery>` encoding, the same one
+/// `Route::search_page_from_query` builds URLs with, so recalling a saved
+/// search is just dropping this string straight back into
+/// `Route::SearchPage`'s `query` parameter with nothing to re-derive.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: String,
+    pub name: String,
+    pub encoded_query: String,
+    pub date_created: String,
+    /// Hit count as of the last time this search was saved, so
+    /// investigators monitoring an evolving document set can spot when a
+    /// standing query has grown since they last looked.
+    pub last_hit_count: Option<u64>,
+}
@@ -0,0 +1,42 @@
+//! Shared constants used by both the frontend and backend search code.
+
+/// Number of documents shown per search results page.
+pub const PAGE_SIZE: u64 = 20;
+
+/// Hard ceiling on how far pagination/export cursors are allowed to walk
+/// through a result set, to keep a single runaway query bounded.
+pub const MAX_PAGINATION_DOCUMENT_LIMIT: u64 = 10_000;
+
+/// Number of facet values shown per "page" in a facet popover, both for the
+/// initial load and each subsequent "show more" click.
+pub const FACET_VALUES_PAGE_SIZE: u64 = 30;
+
+/// Upper bound on how many facet buckets are pulled from Manticore for a
+/// single facet so that filtering/pagination can happen in-process without
+/// re-querying on every keystroke, while still bounding a single query.
+pub const FACET_VALUES_FETCH_CAP: u64 = 1000;
+
+/// Default max words kept per snippet fragment when a `SearchQuery` doesn't
+/// request a specific `crop_length`, matching Manticore's own `HIGHLIGHT()`
+/// `limit_words` option.
+pub const DEFAULT_CROP_LENGTH: u32 = 30;
+
+/// Default words of context kept on each side of a highlighted match when a
+/// `SearchQuery` doesn't request a specific `snippet_around`, matching
+/// Manticore's own `HIGHLIGHT()` `around` option.
+pub const DEFAULT_SNIPPET_AROUND: u32 = 50;
+
+/// Relative trust given to a text extractor's output when ranking which
+/// `extracted_by` source to auto-select for a document: embedded/native
+/// text layers are far less noisy than OCR, so they win ties on raw hit
+/// count.
+pub fn extractor_quality_weight(extracted_by: &str) -> f64 {
+    let extracted_by = extracted_by.to_lowercase();
+    if extracted_by.contains("embedded") || extracted_by.contains("native") || extracted_by.contains("pdftotext") {
+        1.5
+    } else if extracted_by.contains("tesseract") || extracted_by.contains("ocr") {
+        0.8
+    } else {
+        1.0
+    }
+}
@@ -0,0 +1,34 @@
+//! Shared types for bulk-exporting a search result set.
+
+use serde::{Deserialize, Serialize};
+
+/// Output shape for [`export_search_results`](crate)-style bulk exports of a
+/// search result set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    /// One row per document: `collection_dataset,file_hash,title`.
+    Csv,
+    /// One JSON-encoded document per line.
+    Ndjson,
+    /// A zip archive of the matched documents' original files.
+    Zip,
+}
+
+impl ExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Ndjson => "NDJSON",
+            ExportFormat::Zip => "Zip of original files",
+        }
+    }
+
+    /// File extension used for the downloaded file, without the leading dot.
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Zip => "zip",
+        }
+    }
+}
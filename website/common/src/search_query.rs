@@ -7,10 +7,70 @@ use serde::{Deserialize, Serialize};
 use crate::search_result::FacetOriginalValue;
 
 
+/// How `query_string` is matched against the full-text index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SearchQueryMode {
+    /// Plain bag-of-words `MATCH`, Manticore's default ranking.
+    #[default]
+    Keyword,
+    /// `query_string` is matched as one exact, ordered phrase.
+    Phrase,
+    /// `query_string` is a regular expression matched against the full-text
+    /// columns via Manticore's `REGEX()`.
+    Regex,
+}
+
+impl SearchQueryMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchQueryMode::Keyword => "Keyword",
+            SearchQueryMode::Phrase => "Exact phrase",
+            SearchQueryMode::Regex => "Regex",
+        }
+    }
+
+    /// Cycles to the next mode, for a single-button mode selector.
+    pub fn next(&self) -> SearchQueryMode {
+        match self {
+            SearchQueryMode::Keyword => SearchQueryMode::Phrase,
+            SearchQueryMode::Phrase => SearchQueryMode::Regex,
+            SearchQueryMode::Regex => SearchQueryMode::Keyword,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct SearchQuery {
     pub collection_datasets: Vec<String>,
     pub query_string: String,
+    /// How `query_string` is interpreted. Defaults to
+    /// [`SearchQueryMode::Keyword`].
+    pub query_mode: SearchQueryMode,
     pub facet_filters: BTreeMap<String, BTreeSet<FacetOriginalValue>>,
+    /// Max words kept per highlighted snippet fragment. `None` falls back to
+    /// [`crate::search_const::DEFAULT_CROP_LENGTH`].
+    pub crop_length: Option<u32>,
+    /// Which fields get cropped down to a fragment around the match instead
+    /// of being returned in full. `None` means the caller's usual default
+    /// fields (document text and filenames).
+    pub attributes_to_crop: Option<Vec<String>>,
+    /// Which fields get `<hoover4_strong>` match markers at all. A field
+    /// left out returns its plain stored value with no highlighting.
+    /// `None` means the caller's usual default fields.
+    pub attributes_to_highlight: Option<Vec<String>>,
+    /// Caps how long Manticore spends on this query before returning
+    /// whatever it has gathered so far (`RawSarchResult::timed_out` is then
+    /// set). `None` applies a 60 second limit.
+    pub timeout_ms: Option<u64>,
+    /// Blends full-text `MATCH` scoring with vector similarity: `0.0` (or
+    /// `None`) is pure keyword search, `1.0` is pure semantic search, and
+    /// anything in between fuses both ranked lists via Reciprocal Rank
+    /// Fusion, mirroring Meilisearch's hybrid search `semanticRatio`.
+    pub semantic_ratio: Option<f32>,
+    /// Words of context kept on each side of a highlighted match inside a
+    /// snippet fragment. `None` falls back to
+    /// [`crate::search_const::DEFAULT_SNIPPET_AROUND`], matching
+    /// Manticore's own `HIGHLIGHT()` `around` option.
+    pub snippet_around: Option<u32>,
 }
\ No newline at end of file
@@ -10,6 +10,17 @@ pub struct SearchResultDocuments {
     pub prev_hash: Option<DocumentIdentifier>,
     pub next_hash: Option<DocumentIdentifier>,
     pub page_number: u64,
+    /// True when Manticore hit `query.timeout_ms` (or the engine default)
+    /// before finishing, so `results` above may be missing hits that a
+    /// completed query would have included.
+    pub timed_out: bool,
+    /// True when every shard of this response was served from
+    /// `search_manticore_cache` instead of hitting Manticore.
+    pub cache_hit: bool,
+    /// Wall-clock time of the underlying Manticore search, in milliseconds:
+    /// the original query's duration on a cache hit, or this request's own
+    /// on a miss. The slowest shard's duration in federated mode.
+    pub cache_duration_ms: u32,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Hash, Eq, PartialOrd, Ord)]
@@ -27,6 +38,11 @@ pub struct SearchResultDocumentItem {
     pub file_hash: String,
     pub collection_dataset: String,
     pub result_index_in_page: u64,
+    /// False until the document's highlight snippet has arrived from the
+    /// `stream_search_snippets` follow-up, so callers know whether the
+    /// `highlight_*_spans` fields above are real or still just empty
+    /// placeholders while `search_for_results` returns the page instantly.
+    pub snippets_loaded: bool,
 }
 
 impl SearchResultDocumentItem {
@@ -38,11 +54,41 @@ impl SearchResultDocumentItem {
     }
 }
 
+/// Snippet payload streamed lazily per document by `stream_search_snippets`,
+/// once `search_for_results` has already returned the cheap page of titles.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResultSnippet {
+    pub collection_dataset: String,
+    pub file_hash: String,
+    pub highlight_text_spans: Vec<HighlightTextSpan>,
+    pub highlight_filenames_spans: Vec<HighlightTextSpan>,
+    /// Number of highlighted hits in `highlight_text_spans`, within this
+    /// snippet's cropped window (not a whole-document match count).
+    pub match_count: u64,
+    /// Best-guess page to deep-link straight to for this document's top
+    /// match, taken from the page of the single best-matching row the
+    /// snippet's text was drawn from.
+    pub page_id: Option<u32>,
+}
+
+impl SearchResultSnippet {
+    pub fn document_identifier(&self) -> DocumentIdentifier {
+        DocumentIdentifier {
+            collection_dataset: self.collection_dataset.clone(),
+            file_hash: self.file_hash.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SearchResultFacets {
     pub query: SearchQuery,
     pub facet_field: String,
     pub facet_values: Vec<SearchResultFacetItem>,
+    /// True when more values exist beyond `facet_values`, either because the
+    /// search text matched more than fit in this page or because the
+    /// requested `limit` was smaller than the number of available values.
+    pub has_more: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -52,6 +98,21 @@ pub struct SearchResultFacetItem {
     pub count: u64,
 }
 
+/// Stats/distribution for a numeric facet column (e.g. file size, date),
+/// analogous to Meilisearch's facet stats: the `[min, max]` range under the
+/// current query plus a bucketed histogram across it, for rendering a
+/// client-side range slider that a discrete bucket list from
+/// `SearchResultFacets` can't.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResultFacetStats {
+    pub facet_field: String,
+    pub min: f64,
+    pub max: f64,
+    /// `(bucket_low, bucket_high, doc_count)` for each equal-width bucket
+    /// spanning `[min, max]`.
+    pub histogram: Vec<(f64, f64, u64)>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialOrd, Ord, PartialEq, Eq)]
 pub enum FacetOriginalValue {
     String(String),
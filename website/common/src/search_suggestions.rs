@@ -0,0 +1,17 @@
+//! Shared types for query autocomplete / "did you mean" suggestions.
+
+use serde::{Deserialize, Serialize};
+
+/// A single fuzzy-correction candidate for the last token of a query, from
+/// Manticore's `CALL SUGGEST`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub term: String,
+    /// Levenshtein edit distance from the typed token; `0` means an exact
+    /// match already exists in the corpus.
+    pub distance: u32,
+    /// Number of distinct documents containing `term`.
+    pub docs: u64,
+    /// Total number of occurrences of `term` across the corpus.
+    pub hits: u64,
+}
@@ -8,4 +8,17 @@ pub struct HighlightTextSpan {
     pub text: String,
     pub is_highlighted: bool,
     pub index: u64,
-}
\ No newline at end of file
+    /// Stable position of the query term this span matched, within the
+    /// originating search query's distinct terms (quoted phrases count as
+    /// one term). `None` when the span is highlighted but couldn't be
+    /// attributed to a specific term with confidence.
+    pub term_index: Option<usize>,
+    /// The query term text `term_index` refers to, for frontend labelling
+    /// (e.g. a color-key legend).
+    pub term_text: Option<String>,
+    /// The source page this span was decomposed from, when the caller knows
+    /// it (e.g. a single-page text search). `None` when the span comes from
+    /// a multi-page aggregate with no single originating page, such as the
+    /// per-document grouped highlight of the unified search preview.
+    pub page_id: Option<u32>,
+}
@@ -0,0 +1,39 @@
+//! Shared types for the search top-bar's cross-provider "unified search"
+//! preview, which fans a query out to content, filename and metadata
+//! matches at once instead of forcing the user to pick a search mode.
+
+use serde::{Deserialize, Serialize};
+
+use crate::search_result::SearchResultDocumentItem;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnifiedSearchProvider {
+    Content,
+    Filename,
+    Metadata,
+}
+
+impl UnifiedSearchProvider {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UnifiedSearchProvider::Content => "Content matches",
+            UnifiedSearchProvider::Filename => "Filename matches",
+            UnifiedSearchProvider::Metadata => "Metadata matches",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnifiedSearchResultGroup {
+    pub provider: UnifiedSearchProvider,
+    pub results: Vec<SearchResultDocumentItem>,
+    /// Total number of documents matching this provider, which may be
+    /// larger than `results.len()` since the preview only fetches a few
+    /// per provider.
+    pub total_count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnifiedSearchResults {
+    pub groups: Vec<UnifiedSearchResultGroup>,
+}
@@ -0,0 +1,9 @@
+//! Client API calls for admin-only maintenance endpoints.
+
+use dioxus::prelude::*;
+
+#[server]
+pub async fn purge_search_cache(query_substring: Option<String>, older_than_seconds: Option<u32>) -> Result<(), ServerFnError> {
+    let x = backend::api::admin::purge_search_cache(query_substring, older_than_seconds).await;
+    x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
+}
@@ -0,0 +1,16 @@
+//! Client API calls for the file browser endpoints.
+
+use common::file_browser::FileBrowserEntry;
+use dioxus::prelude::*;
+
+#[server]
+pub async fn list_collections() -> Result<Vec<String>, ServerFnError> {
+    let x = backend::api::file_browser::list_collections().await;
+    x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
+}
+
+#[server]
+pub async fn list_directory_entries(collection_dataset: String, path_prefix: String) -> Result<Vec<FileBrowserEntry>, ServerFnError> {
+    let x = backend::api::file_browser::list_directory_entries(collection_dataset, path_prefix).await;
+    x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
+}
@@ -0,0 +1,22 @@
+//! Client API calls for the saved-searches subsystem.
+
+use common::saved_search::SavedSearch;
+use dioxus::prelude::*;
+
+#[server]
+pub async fn save_search(name: String, encoded_query: String, last_hit_count: Option<u64>) -> Result<SavedSearch, ServerFnError> {
+    let x = backend::api::saved_searches::save_search(name, encoded_query, last_hit_count).await;
+    x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
+}
+
+#[server]
+pub async fn list_saved_searches() -> Result<Vec<SavedSearch>, ServerFnError> {
+    let x = backend::api::saved_searches::list_saved_searches().await;
+    x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
+}
+
+#[server]
+pub async fn delete_saved_search(id: String) -> Result<(), ServerFnError> {
+    let x = backend::api::saved_searches::delete_saved_search(id).await;
+    x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
+}
@@ -1,10 +1,12 @@
 //! Client API calls for search endpoints.
 
-use common::{search_query::SearchQuery, search_result::{SearchResultDocuments, SearchResultFacets}};
+use common::{search_export::ExportFormat, search_query::SearchQuery, search_result::{DocumentIdentifier, SearchResultDocumentItem, SearchResultDocuments, SearchResultFacets, SearchResultSnippet}, search_suggestions::Suggestion, unified_search::UnifiedSearchResults};
 use dioxus::prelude::*;
+use server_fn::codec::{StreamingText, TextStream};
 
-
-
+fn snippet_stream_error(e: impl ToString) -> ServerFnError {
+    ServerFnError::ServerError { message: e.to_string(), code: 500, details: None }
+}
 
 #[server]
 pub async fn search_for_results(input: SearchQuery, current_search_result_page: u64) -> Result<SearchResultDocuments, ServerFnError> {
@@ -19,7 +21,67 @@ pub async fn search_for_results_hit_count(input: SearchQuery) -> Result<u64, Ser
 }
 
 #[server]
-pub async fn search_string_facet(input: SearchQuery, column: String, map_string_terms: Option<String>) -> Result<SearchResultFacets, ServerFnError> {
-    let x = backend::api::search::search_string_facet(input, column, map_string_terms).await;
+pub async fn search_string_facet(input: SearchQuery, column: String, map_string_terms: Option<String>, facet_search_text: Option<String>, limit: u64) -> Result<SearchResultFacets, ServerFnError> {
+    let x = backend::api::search::search_string_facet(input, column, map_string_terms, facet_search_text, limit).await;
+    x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
+}
+
+#[server]
+pub async fn search_unified(input: SearchQuery) -> Result<UnifiedSearchResults, ServerFnError> {
+    let x = backend::api::search::search_unified(input).await;
     x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
 }
+
+/// Fuzzy "did you mean" corrections for the last token of `prefix`, for the
+/// search box's autocomplete dropdown.
+#[server]
+pub async fn search_suggestions(prefix: String, limit: u32) -> Result<Vec<Suggestion>, ServerFnError> {
+    let x = backend::api::search::search_suggestions(prefix, limit).await;
+    x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
+}
+
+/// "More like this": documents ranked by embedding similarity to
+/// `document_identifier` instead of a text query.
+#[server]
+pub async fn search_similar(document_identifier: DocumentIdentifier, limit: u64) -> Result<Vec<SearchResultDocumentItem>, ServerFnError> {
+    let x = backend::api::search::search_similar(document_identifier, limit).await;
+    x.map_err(|e| ServerFnError::ServerError { message: e.to_string(), code: 500, details: None })
+}
+
+/// Phase two of the two-phase result page: streams one JSON-encoded
+/// [`SearchResultSnippet`] line at a time as each document's `HIGHLIGHT`
+/// query completes, so the page returned by `search_for_results` can fill in
+/// highlighted snippets progressively instead of waiting on all of them.
+#[server(output = StreamingText)]
+pub async fn stream_search_snippets(input: SearchQuery, current_search_result_page: u64) -> Result<TextStream, ServerFnError> {
+    use futures::StreamExt;
+
+    let stream = backend::api::search::stream_search_snippets(input, current_search_result_page)
+        .await
+        .map_err(snippet_stream_error)?;
+
+    let text_stream = stream.map(|snippet| {
+        let snippet: SearchResultSnippet = snippet.map_err(snippet_stream_error)?;
+        serde_json::to_string(&snippet).map_err(snippet_stream_error)
+    });
+
+    Ok(TextStream::new(text_stream))
+}
+
+/// Bulk export of an entire result set (not just one page): `Csv`/`Ndjson`
+/// stream one plain-text line per document as `export_search_results`
+/// walks its pagination cursor, and `Zip` streams the finished archive back
+/// as base64-encoded text lines so it fits through the same text-streaming
+/// codec.
+#[server(output = StreamingText)]
+pub async fn export_search_results(query: SearchQuery, format: ExportFormat) -> Result<TextStream, ServerFnError> {
+    use futures::StreamExt;
+
+    let stream = backend::api::search::export_search_results(query, format)
+        .await
+        .map_err(snippet_stream_error)?;
+
+    let text_stream = stream.map(|line| line.map_err(snippet_stream_error));
+
+    Ok(TextStream::new(text_stream))
+}
@@ -0,0 +1,132 @@
+//! Syntax-highlighted source-code viewer, the `Code` sibling of
+//! `PDFDataViewer`/`TextDataViewer` picked by `get_document_type` for config
+//! files, logs and code embedded in seized archives.
+
+use common::code_highlight::{CodeHighlightLine, CodeHighlightResponse, CodeHighlightTheme, CodeTokenClass};
+use common::search_result::DocumentIdentifier;
+use dioxus::prelude::*;
+
+use crate::components::suspend_boundary::LoadingIndicator;
+use crate::data_definitions::doc_viewer_state::DocViewerState;
+use crate::pages::search_page::DocViewerStateControl;
+
+#[component]
+pub fn CodeDataViewer(document_identifier: ReadSignal<DocumentIdentifier>) -> Element {
+    let control = use_context::<DocViewerStateControl>();
+    let theme = use_memo(move || control.doc_viewer_state.read().as_ref().map(|state| state.code_theme).unwrap_or_default());
+
+    let code = use_resource(move || {
+        let document_identifier = document_identifier.read().clone();
+        get_code_highlight(document_identifier)
+    });
+
+    let toggle_theme = move |_| {
+        let mut state = control.doc_viewer_state.read().clone().unwrap_or_else(|| DocViewerState::from_find_query("".to_string()));
+        state.code_theme = state.code_theme.toggled();
+        control.set_doc_viewer_state.call(state);
+    };
+
+    let response = match code.read().clone() {
+        Some(Ok(response)) => response,
+        Some(Err(e)) => {
+            return rsx! {
+                pre {
+                    style: "color:red; font-size: 16px; border: 1px solid red; padding: 10px; border-radius: 5px; margin: 15px;",
+                    "{e:#?}"
+                }
+            }
+        }
+        None => {
+            return rsx! {
+                div {
+                    style: "width: 90%; height: 60px;",
+                    LoadingIndicator {}
+                }
+            }
+        }
+    };
+
+    let (background_color, text_color) = theme_colors(theme());
+
+    rsx! {
+        div {
+            style: "display: flex; flex-direction: column; height: 100%; width: 100%; background-color: {background_color};",
+            div {
+                style: "
+                    display: flex;
+                    justify-content: space-between;
+                    align-items: center;
+                    padding: 6px 12px;
+                    border-bottom: 1px solid rgba(128, 128, 128, 0.3);
+                    color: {text_color};
+                ",
+                span { style: "font-size: 13px; opacity: 0.7;", "{response.language}" }
+                button {
+                    style: "
+                        background: none;
+                        border: 1px solid rgba(128, 128, 128, 0.4);
+                        border-radius: 4px;
+                        padding: 2px 8px;
+                        cursor: pointer;
+                        color: {text_color};
+                    ",
+                    onclick: toggle_theme,
+                    if theme() == CodeHighlightTheme::Light { "🌙 Dark" } else { "☀️ Light" }
+                }
+            }
+            div {
+                style: "flex-grow: 1; overflow: auto;",
+                for line in response.lines.iter().cloned() {
+                    CodeLine { line, theme: theme() }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn CodeLine(line: CodeHighlightLine, theme: CodeHighlightTheme) -> Element {
+    rsx! {
+        div {
+            id: "code-line-{line.line_number}",
+            style: "display: flex; font-family: monospace; font-size: 13px; line-height: 20px; white-space: pre;",
+            span {
+                style: "display: inline-block; min-width: 48px; text-align: right; padding-right: 12px; opacity: 0.4; user-select: none;",
+                "{line.line_number}"
+            }
+            span {
+                style: "flex: 1;",
+                for token in line.tokens.iter().cloned() {
+                    span { style: "color: {token_color(token.class, theme)};", "{token.text}" }
+                }
+            }
+        }
+    }
+}
+
+fn theme_colors(theme: CodeHighlightTheme) -> (&'static str, &'static str) {
+    match theme {
+        CodeHighlightTheme::Light => ("#ffffff", "#1a1a1a"),
+        CodeHighlightTheme::Dark => ("#1e1e1e", "#d4d4d4"),
+    }
+}
+
+fn token_color(class: CodeTokenClass, theme: CodeHighlightTheme) -> &'static str {
+    match (theme, class) {
+        (CodeHighlightTheme::Light, CodeTokenClass::Plain) => "#1a1a1a",
+        (CodeHighlightTheme::Light, CodeTokenClass::Comment) => "#6a9955",
+        (CodeHighlightTheme::Light, CodeTokenClass::String) => "#a31515",
+        (CodeHighlightTheme::Light, CodeTokenClass::Number) => "#098658",
+        (CodeHighlightTheme::Light, CodeTokenClass::Keyword) => "#0000ff",
+        (CodeHighlightTheme::Dark, CodeTokenClass::Plain) => "#d4d4d4",
+        (CodeHighlightTheme::Dark, CodeTokenClass::Comment) => "#6a9955",
+        (CodeHighlightTheme::Dark, CodeTokenClass::String) => "#ce9178",
+        (CodeHighlightTheme::Dark, CodeTokenClass::Number) => "#b5cea8",
+        (CodeHighlightTheme::Dark, CodeTokenClass::Keyword) => "#569cd6",
+    }
+}
+
+#[server]
+async fn get_code_highlight(document_identifier: DocumentIdentifier) -> Result<CodeHighlightResponse, ServerFnError> {
+    backend::api::documents::get_code_highlight::get_code_highlight(document_identifier).await.map_err(|e| ServerFnError::from(e))
+}
@@ -1,44 +1,78 @@
-use common::document_text_sources::{DocumentTextSourceHit, DocumentTextSourceHitCount, DocumentTextSourceItem};
-use common::pdf_to_html_conversion::PDFToHtmlConversionResponse;
-use dioxus::logger::tracing;
+//! Continuous-scroll PDF preview, the `Pdf` sibling of `TextDataViewer`/
+//! `CodeDataViewer` picked by `get_document_type`. Renders a windowed slice
+//! of pages around the viewport instead of the whole document, recentering
+//! the window as the user scrolls past its edges.
+
+use common::pdf_to_html_conversion::PDFPageRangeResponse;
 use dioxus::prelude::*;
-use common::search_query::SearchQuery;
 use common::search_result::DocumentIdentifier;
 
-use crate::components::document_view_components::doc_title_bar::DocTitleBar;
-use crate::components::document_view_components::raw_metadata_collector::RawMetadataCollector;
+use crate::components::document_view_components::math_render::katex_iframe_script;
 use crate::components::suspend_boundary::LoadingIndicator;
+use crate::data_definitions::doc_viewer_state::DocViewerState;
 use crate::pages::search_page::DocViewerStateControl;
 
+/// Pages kept loaded on either side of the current page, so scrolling a
+/// page or two never has to wait on a round trip.
+const PREFETCH_RADIUS: u32 = 2;
+const WINDOW_SIZE: u32 = PREFETCH_RADIUS * 2 + 1;
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 4.0;
+const ZOOM_STEP: f32 = 0.25;
 
-
-#[server]
-pub async fn get_document_type_is_pdf(document_identifier: DocumentIdentifier) -> Result<(bool, u32), ServerFnError> {
-    let (is_pdf, page_count) = backend::api::documents::get_pdf_to_html_conversion::get_document_type_is_pdf(document_identifier).await.map_err(|e| ServerFnError::from(e))?;
-    Ok((is_pdf, page_count))
-}
-
+const SCROLL_CONTAINER_ID: &str = "pdf-scroll-container";
 
 #[component]
 pub fn DocumentPreviewForPdf(
     document_identifier: ReadSignal<DocumentIdentifier>,
-    page_count: ReadSignal<u32>,
 ) -> Element {
-    let current_page_index = use_signal(move || 0_u32);
-    let pdf_to_html_conversion = use_resource(move || {
-        let document_identifier = document_identifier.read().clone();
-        let current_page_index = current_page_index.read().clone();
-        async move {
-            let pdf_to_html_conversion = get_pdf_to_html_single_page(document_identifier, current_page_index).await;
-            pdf_to_html_conversion
+    let control = use_context::<DocViewerStateControl>();
+    let persisted = move || control.doc_viewer_state.read().clone();
+
+    let mut current_page_index = use_signal(move || persisted().and_then(|state| state.pdf_page_index).unwrap_or(0));
+    let mut zoom_level = use_signal(move || persisted().and_then(|state| state.pdf_zoom_level).unwrap_or(1.0));
+
+    let mut last_doc_id = use_signal(move || document_identifier.peek().clone());
+    use_effect(move || {
+        let doc_id = document_identifier.read().clone();
+        if *last_doc_id.peek() != doc_id {
+            last_doc_id.set(doc_id);
+            current_page_index.set(0);
         }
     });
-    let data_viewer = match pdf_to_html_conversion.read().clone() {
-        Some(Ok(pdf_to_html_conversion)) => {
-            rsx! {
-                PDFDataViewer { pdf_to_html_conversion }
-            }
+
+    // Persist page/zoom so the view survives navigating away and back.
+    use_effect(move || {
+        let page = *current_page_index.read();
+        let zoom = *zoom_level.read();
+        let mut state = control.doc_viewer_state.read().clone().unwrap_or_else(|| DocViewerState::from_find_query("".to_string()));
+        if state.pdf_page_index != Some(page) || state.pdf_zoom_level != Some(zoom) {
+            state.pdf_page_index = Some(page);
+            state.pdf_zoom_level = Some(zoom);
+            control.set_doc_viewer_state.call(state);
+        }
+    });
+
+    // The window only recenters once the current page nears either edge, so
+    // scrolling within the middle of an already-fetched window doesn't
+    // trigger a refetch.
+    let mut window_start = use_signal(move || current_page_index().saturating_sub(PREFETCH_RADIUS));
+    use_effect(move || {
+        let current = *current_page_index.read();
+        let start = *window_start.read();
+        if current <= start + 1 || current >= start + WINDOW_SIZE - 2 {
+            window_start.set(current.saturating_sub(PREFETCH_RADIUS));
         }
+    });
+
+    let page_range = use_resource(move || {
+        let document_identifier = document_identifier.read().clone();
+        let start = *window_start.read();
+        async move { get_pdf_to_html_page_range(document_identifier, start, WINDOW_SIZE).await }
+    });
+
+    let response = match page_range.read().clone() {
+        Some(Ok(response)) => response,
         Some(Err(e)) => {
             return rsx! {
                 pre {
@@ -56,16 +90,71 @@ pub fn DocumentPreviewForPdf(
             }
         }
     };
+    let page_count = response.page_count;
+
     rsx! {
-        {data_viewer}
-        PdfControllerOverlay { page_count, current_page_index }
+        PDFDataViewer { response, current_page_index, zoom_level }
+        PdfControllerOverlay { page_count, current_page_index, zoom_level }
     }
 }
 
+/// Jumps to `target`, both updating the page signal and smooth-scrolling
+/// the corresponding page element into view. Only used for
+/// programmatically-triggered page changes (the overlay's buttons) -
+/// scroll-driven updates go through `current_page_index.set` directly so
+/// the two don't fight each other.
+fn go_to_page(mut current_page_index: Signal<u32>, target: u32) {
+    current_page_index.set(target);
+    spawn(async move {
+        let script = format!(
+            r#"
+            const el = document.querySelector('.pdf-page[data-page-index="{target}"]');
+            if (el) {{ el.scrollIntoView({{ behavior: "smooth", block: "start" }}); }}
+            "#
+        );
+        document::eval(&script);
+    });
+}
+
+/// Reads which rendered `.pdf-page` is closest to the top of the scroll
+/// container and updates `current_page_index` to match, the same role an
+/// `IntersectionObserver` would play, without needing a persistent JS-side
+/// observer for a window this small.
+fn update_visible_page(mut current_page_index: Signal<u32>) {
+    spawn(async move {
+        let script = format!(
+            r#"
+            (() => {{
+                const container = document.getElementById("{SCROLL_CONTAINER_ID}");
+                if (!container) {{ dioxus.send(null); return; }}
+                const containerTop = container.getBoundingClientRect().top;
+                let best = null;
+                let bestDist = Infinity;
+                for (const el of container.querySelectorAll(".pdf-page")) {{
+                    const rect = el.getBoundingClientRect();
+                    const dist = Math.abs(rect.top - containerTop);
+                    if (rect.bottom > containerTop && dist < bestDist) {{
+                        bestDist = dist;
+                        best = parseInt(el.dataset.pageIndex, 10);
+                    }}
+                }}
+                dioxus.send(best);
+            }})();
+            "#
+        );
+        let mut eval = document::eval(&script);
+        if let Ok(Some(page_index)) = eval.recv::<Option<u32>>().await {
+            if page_index != *current_page_index.read() {
+                current_page_index.set(page_index);
+            }
+        }
+    });
+}
+
 #[component]
-fn PdfControllerOverlay(page_count: ReadSignal<u32>, current_page_index: Signal<u32>) -> Element {
-    let mut current_page = current_page_index;
+fn PdfControllerOverlay(page_count: ReadSignal<u32>, mut current_page_index: Signal<u32>, mut zoom_level: Signal<f32>) -> Element {
     let page_count = page_count();
+    let zoom_percent = (*zoom_level.read() * 100.0).round() as u32;
 
     rsx! {
         div {
@@ -75,7 +164,7 @@ fn PdfControllerOverlay(page_count: ReadSignal<u32>, current_page_index: Signal<
 
                 div {
                     style: "font-size: 14px; font-weight: bold; margin-bottom: 4px; padding: 4px; border-bottom: 1px solid #eee; width: 100%; text-align: center;",
-                    "{current_page() + 1}"
+                    "{current_page_index() + 1}"
                 }
 
                 div {
@@ -86,8 +175,8 @@ fn PdfControllerOverlay(page_count: ReadSignal<u32>, current_page_index: Signal<
                 button {
                     style: "background: none; border: none; cursor: pointer; font-size: 20px; padding: 4px; margin: 2px 0;",
                     onclick: move |_| {
-                        if current_page() > 0 {
-                            current_page -= 1;
+                        if current_page_index() > 0 {
+                            go_to_page(current_page_index, current_page_index() - 1);
                         }
                     },
                     "🔼"
@@ -96,92 +185,123 @@ fn PdfControllerOverlay(page_count: ReadSignal<u32>, current_page_index: Signal<
                 button {
                     style: "background: none; border: none; cursor: pointer; font-size: 20px; padding: 4px; margin: 2px 0;",
                     onclick: move |_| {
-                        if current_page() < page_count - 1 {
-                            current_page += 1;
+                        if current_page_index() < page_count - 1 {
+                            go_to_page(current_page_index, current_page_index() + 1);
                         }
                     },
                     "🔽"
                 }
 
+                div { style: "width: 100%; border-top: 1px solid #eee; margin: 4px 0;" }
+
                 button {
-                    style: "background: none; border: none; cursor: default; font-size: 20px; padding: 4px; margin: 2px 0; opacity: 0.3;",
-                    disabled: true,
+                    style: "background: none; border: none; cursor: pointer; font-size: 20px; padding: 4px; margin: 2px 0;",
+                    disabled: *zoom_level.read() >= MAX_ZOOM,
+                    onclick: move |_| {
+                        let next = (*zoom_level.read() + ZOOM_STEP).min(MAX_ZOOM);
+                        zoom_level.set(next);
+                    },
                     "➕"
                 }
 
+                div {
+                    style: "font-size: 11px; color: #666; margin-bottom: 2px;",
+                    "{zoom_percent}%"
+                }
+
                 button {
-                    style: "background: none; border: none; cursor: default; font-size: 20px; padding: 4px; margin: 2px 0; opacity: 0.3;",
-                    disabled: true,
+                    style: "background: none; border: none; cursor: pointer; font-size: 20px; padding: 4px; margin: 2px 0;",
+                    disabled: *zoom_level.read() <= MIN_ZOOM,
+                    onclick: move |_| {
+                        let next = (*zoom_level.read() - ZOOM_STEP).max(MIN_ZOOM);
+                        zoom_level.set(next);
+                    },
                     "➖"
                 }
             }
         }
     }
 }
-#[component]
-fn PDFDataViewer(pdf_to_html_conversion: ReadSignal<PDFToHtmlConversionResponse>) -> Element {
-    let page_width_px = use_memo(move || {
-        pdf_to_html_conversion.read().page_width_px
-    });
-    let page_height_px = use_memo(move || {
-        pdf_to_html_conversion.read().page_height_px
-    });
-    let aspect_ratio = use_memo(move || {
-        page_width_px() / page_height_px()
-    });
 
-    let html_content = use_memo(move || {
-        let styles = pdf_to_html_conversion.read().clone().styles.join("\n");
-        let page_idx = 0;
-        let page_content = pdf_to_html_conversion.read().clone().pages[page_idx].clone();
-        let page_content = format!("{styles}\n{page_content}");
-
-        rsx! {
-            iframe {
-                srcdoc: "{page_content}",
-                style: "width: {page_width_px+60.0}px; height: {page_height_px+60.0}px;  aspect-ratio: {aspect_ratio};",
-            }
-        }
-    });
+#[component]
+fn PDFDataViewer(response: ReadSignal<PDFPageRangeResponse>, current_page_index: Signal<u32>, zoom_level: Signal<f32>) -> Element {
+    let page_width_px = use_memo(move || response.read().page_width_px);
+    let page_height_px = use_memo(move || response.read().page_height_px);
+    let aspect_ratio = use_memo(move || page_width_px() / page_height_px());
 
     let mut resize_info = use_signal(move || (page_width_px(), page_height_px()));
-    let mut scale_factor = use_memo(move || {
+    let scale_factor = use_memo(move || {
         let rx = resize_info.read().0 / (page_width_px() + 60.0);
         let ry = resize_info.read().1 / (page_height_px() + 60.0);
-        let min_scale_factor = rx.min(ry);
-        min_scale_factor
+        let fit_scale = rx.min(ry);
+        fit_scale * *zoom_level.read()
     });
 
-
+    let start_page = response.read().start_page;
+    let has_math = response.read().has_math;
+    let styles = response.read().styles.join("\n");
+    let math_script = if has_math { katex_iframe_script() } else { String::new() };
+    let page_count = response.read().page_count;
 
     rsx! {
         div {
-            style: "height: 50px; font-size: 40px;",
-            "TODO HEADER"
+            style: "height: 50px; font-size: 20px; display: flex; align-items: center; padding: 0 16px; opacity: 0.6;",
+            "Page {current_page_index() + 1} of {page_count}"
         }
         div {
-            style: "aspect-ratio: {aspect_ratio};width: 100%;height: calc(100% - 50px);",
+            id: "{SCROLL_CONTAINER_ID}",
+            style: "width: 100%; height: calc(100% - 50px); overflow-y: auto;",
             onresize: move |e| {
-                let Ok(size) = e.data().clone().get_border_box_size() else {
-                    tracing::error!("Failed to get border box size: {:#?}", e.data());
-                    return;
-                };
-                // tracing::info!("Border box size: {:#?}", size);
-
+                let Ok(size) = e.data().clone().get_border_box_size() else { return };
                 resize_info.set((size.width as f32, size.height as f32));
             },
+            onscroll: move |_| update_visible_page(current_page_index),
+            for (offset, page_content) in response.read().pages.iter().cloned().enumerate() {
+                PdfPage {
+                    key: "{start_page + offset as u32}",
+                    page_index: start_page + offset as u32,
+                    page_content,
+                    styles: styles.clone(),
+                    math_script: math_script.clone(),
+                    page_width_px: page_width_px(),
+                    page_height_px: page_height_px(),
+                    aspect_ratio: aspect_ratio(),
+                    scale_factor: scale_factor(),
+                }
+            }
+        }
+    }
+}
 
-            div {
-                style: "transform: scale({scale_factor}); transform-origin: top left;",
-                {html_content()}
+#[component]
+fn PdfPage(
+    page_index: u32,
+    page_content: String,
+    styles: String,
+    math_script: String,
+    page_width_px: f32,
+    page_height_px: f32,
+    aspect_ratio: f32,
+    scale_factor: f32,
+) -> Element {
+    let content = format!("{styles}\n{page_content}\n{math_script}");
+
+    rsx! {
+        div {
+            class: "pdf-page",
+            "data-page-index": "{page_index}",
+            style: "margin: 0 auto 16px auto; width: {page_width_px+60.0}px; transform: scale({scale_factor}); transform-origin: top center;",
+            iframe {
+                srcdoc: "{content}",
+                style: "width: {page_width_px+60.0}px; height: {page_height_px+60.0}px; aspect-ratio: {aspect_ratio}; border: none; display: block;",
             }
         }
     }
 }
 
 #[server]
-async fn get_pdf_to_html_single_page(document_identifier: DocumentIdentifier, page_index: u32) -> Result<PDFToHtmlConversionResponse, ServerFnError> {
-    let pdf_to_html_conversion = backend::api::documents::get_pdf_to_html_conversion::
-    get_pdf_to_html_single_page(document_identifier, page_index).await.map_err(|e| ServerFnError::from(e));
-    pdf_to_html_conversion
+async fn get_pdf_to_html_page_range(document_identifier: DocumentIdentifier, start_page: u32, window_size: u32) -> Result<PDFPageRangeResponse, ServerFnError> {
+    backend::api::documents::get_pdf_to_html_conversion::get_pdf_to_html_page_range(document_identifier, start_page, window_size)
+        .await
+        .map_err(|e| ServerFnError::from(e))
 }
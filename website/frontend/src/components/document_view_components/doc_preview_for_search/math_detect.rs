@@ -0,0 +1,65 @@
+//! Plain-text counterpart to the backend's HTML math-placeholder scanner
+//! (`backend::api::documents::math_placeholders`), used by `TextDataViewer`
+//! to find `$...$`/`$$...$$` spans in extracted document text. There's no
+//! markup to dodge here, so this only needs to report char-offset ranges for
+//! the span-merge pass to carve out.
+
+/// A detected math span's char-offset range (half-open, matching
+/// [`str::chars`] indexing) plus its TeX source and whether it was a block
+/// (`$$...$$`) or inline (`$...$`) delimiter.
+pub struct MathRange {
+    pub start: usize,
+    pub end: usize,
+    pub tex_source: String,
+    pub is_block: bool,
+}
+
+/// Balanced-aware like the backend scanner: `\$` is a literal escaped
+/// dollar sign, and a `$`/`$$` with no matching close before the end of the
+/// line (inline) or end of the text (block) is left alone rather than
+/// swallowing the rest of the document.
+pub fn find_math_ranges(text: &str) -> Vec<MathRange> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == '\\' && chars.get(i + 1) == Some(&'$') {
+            i += 2;
+            continue;
+        }
+
+        if chars[i] == '$' {
+            let is_block = chars.get(i + 1) == Some(&'$');
+            let delimiter_len = if is_block { 2 } else { 1 };
+            let search_from = i + delimiter_len;
+            if let Some(end) = find_closing_delimiter(&chars, search_from, delimiter_len) {
+                let tex_source: String = chars[search_from..end].iter().collect();
+                ranges.push(MathRange { start: i, end: end + delimiter_len, tex_source, is_block });
+                i = end + delimiter_len;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    ranges
+}
+
+fn find_closing_delimiter(chars: &[char], from: usize, delimiter_len: usize) -> Option<usize> {
+    let mut i = from;
+    while i + delimiter_len <= chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            // inline math shouldn't be allowed to swallow a whole paragraph
+            // on a stray unmatched `$`
+            '\n' if delimiter_len == 1 => return None,
+            '$' if chars[i..i + delimiter_len].iter().all(|c| *c == '$') && (delimiter_len == 1 || chars.get(i + 1) == Some(&'$')) => {
+                return Some(i);
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
@@ -1,7 +1,12 @@
 mod no_document_selected;
 mod preview_subtitle_bar;
+pub(crate) mod syntax_highlight;
+mod math_detect;
 mod text_data_viewer;
+mod code_data_viewer;
+mod doc_preview_for_pdf;
 
+use common::code_highlight::DocumentType;
 use common::document_text_sources::{DocumentTextSourceHit, DocumentTextSourceHitCount, DocumentTextSourceItem};
 use dioxus::prelude::*;
 use common::search_query::SearchQuery;
@@ -34,11 +39,14 @@ pub struct DocumentViewerResultStore {
     pub current_text_data: ReadSignal<Option<Result<Vec<DocumentTextSourceHit>, ServerFnError>>>,
     pub max_highlighted_word_index: ReadSignal<u32>,
     pub current_highlighted_word_index: Signal<u32>,
+    pub selected_source: ReadSignal<Option<(String, u32)>>,
+    pub set_selected_source: Callback<String>,
+    pub file_extension: ReadSignal<Option<String>>,
 }
 
 
 #[component]
-fn DocumentPreviewForSearch(
+pub(crate) fn DocumentPreviewForSearch(
     document_identifier: ReadSignal<DocumentIdentifier>,
 ) -> Element {
 
@@ -59,6 +67,43 @@ fn DocumentPreviewForSearch(
         all_counts
     });
 
+    // ============== FILE EXTENSION (for syntax highlighting): ==============
+    let mut _file_path_res = use_resource(move || {
+        let _doc_id = document_identifier.read().clone();
+        get_file_path_for_syntax_highlight(_doc_id)
+    });
+    use_effect(move || {
+        let _doc_id = document_identifier.read().clone();
+        _file_path_res.clear();
+        _file_path_res.restart();
+    });
+    let _file_extension_memo = use_memo(move || {
+        let path = _file_path_res.read().clone();
+        let Some(Ok(path)) = path else { return None };
+        let filename = path.rsplit('/').next().unwrap_or(&path);
+        match filename.rsplit_once('.') {
+            Some((_, ext)) if !ext.is_empty() => Some(ext.to_string()),
+            _ => None,
+        }
+    });
+
+    // ============== DOCUMENT TYPE (picks the preview component): ==============
+    let mut _document_type_res = use_resource(move || {
+        let _doc_id = document_identifier.read().clone();
+        get_document_type(_doc_id)
+    });
+    use_effect(move || {
+        let _doc_id = document_identifier.read().clone();
+        _document_type_res.clear();
+        _document_type_res.restart();
+    });
+    let _document_type_memo = use_memo(move || {
+        match _document_type_res.read().clone() {
+            Some(Ok(document_type)) => document_type,
+            _ => DocumentType::Text,
+        }
+    });
+
     // ============== HIT COUNTS: ==============
     let _control_state = use_context::<DocViewerStateControl>().doc_viewer_state;
     let _find_query = use_memo(move || {
@@ -84,29 +129,71 @@ fn DocumentPreviewForSearch(
         Some(hit_counts)
     });
 
+    // ================ MANUAL SOURCE OVERRIDE: ================
+    // Lets the "Source" drop-down in PreviewSubtitleBar pin the preview to a
+    // specific extractor instead of the auto-selected best match. Cleared
+    // whenever the viewed document changes.
+    let mut _source_override = use_signal(move || None::<String>);
+    use_effect(move || {
+        let _doc_id = document_identifier.read().clone();
+        _source_override.set(None);
+    });
+
     // ================ CURRENT SELECTION: ================
     let _current_text_selection: Memo<Option<(String, u32)>> = use_memo(move || {
         let hit_counts = _hit_counts_memo.read().clone();
         let _all_counts = _all_counts_memo.read().clone();
 
-        let Some(mut hit_counts) = hit_counts else { return None };
-        if hit_counts.is_empty() { return _all_counts.first().cloned().map(|item| (item.extracted_by, item.min_page)); }
-        hit_counts.sort_by_key(|h| h.hit_count as i64 * -1);
+        if let Some(extracted_by) = _source_override.read().clone() {
+            let page_id = hit_counts.as_ref()
+                .and_then(|hits| hits.iter().find(|h| h.extracted_by == extracted_by).map(|h| h.page_id))
+                .or_else(|| _all_counts.iter().find(|i| i.extracted_by == extracted_by).map(|i| i.min_page));
+            if let Some(page_id) = page_id {
+                return Some((extracted_by, page_id));
+            }
+        }
 
+        // A deep-linked page pin (e.g. from a search result snippet) wins over
+        // the auto-selected best source, but not over a manual override above.
+        if let Some(pinned_page) = _control_state.read().as_ref().and_then(|state| state.selected_text_page) {
+            let extracted_by = hit_counts.as_ref()
+                .and_then(|hits| hits.iter().find(|h| h.page_id == pinned_page).map(|h| h.extracted_by.clone()))
+                .or_else(|| _all_counts.iter().find(|i| i.min_page == pinned_page).map(|i| i.extracted_by.clone()));
+            if let Some(extracted_by) = extracted_by {
+                return Some((extracted_by, pinned_page));
+            }
+        }
+
+        let Some(hit_counts) = hit_counts else { return None };
+        if hit_counts.is_empty() { return _all_counts.first().cloned().map(|item| (item.extracted_by, item.min_page)); }
+        // hit_counts already comes back ranked by relevance (hit density x
+        // extractor quality), not raw hit_count, so the first entry is the
+        // best source to auto-select.
         return Some((hit_counts[0].extracted_by.clone(), hit_counts[0].page_id));
 
     });
 
     // ================ CURRENT TEXT DATA: ================
+    let _crop_radius = use_memo(move || {
+        let _control_state = _control_state.read().clone();
+        _control_state.map(|state| state.crop_radius).unwrap_or(crate::data_definitions::doc_viewer_state::DEFAULT_CROP_RADIUS)
+    });
+    let _snippet_around = use_memo(move || {
+        let _control_state = _control_state.read().clone();
+        _control_state.map(|state| state.snippet_around).unwrap_or(0)
+    });
     let _current_text_data: Resource<std::result::Result<Vec<DocumentTextSourceHit>, ServerFnError>> = use_resource(move || {
         let _current_text_selection = _current_text_selection.read().clone();
         let document_identifier = document_identifier.read().clone();
         let find_query = _find_query.read().clone();
+        let crop_radius = *_crop_radius.read();
+        let snippet_around = *_snippet_around.read();
+        let snippet_limit = if snippet_around == 0 { 0 } else { crate::data_definitions::doc_viewer_state::DEFAULT_SNIPPET_LIMIT };
         async move {
             let Some((extracted_by, page_id)) = _current_text_selection else {
                 return Err(ServerFnError::from(anyhow::anyhow!("No current text selection"))) };
             let item = search_document_text_for_hits(
-                document_identifier, find_query, extracted_by, page_id).await;
+                document_identifier, find_query, extracted_by, page_id, crop_radius, snippet_around, snippet_limit).await;
             item
         }
     });
@@ -129,7 +216,11 @@ fn DocumentPreviewForSearch(
     let mut current_highlighted_word_index = use_signal(move || 0);
     use_effect(move || {
         let _max = *max_highlighted_word_index.read();
-        current_highlighted_word_index.set(0);
+        // Deep-linking seeds the viewer at a specific hit (e.g. a search
+        // result snippet's top match) instead of always snapping to the
+        // first one.
+        let seed = _control_state.read().as_ref().and_then(|state| state.match_index).unwrap_or(0);
+        current_highlighted_word_index.set(seed as u32);
     });
 
 
@@ -139,6 +230,11 @@ fn DocumentPreviewForSearch(
         current_text_data: _current_text_data.into(),
         max_highlighted_word_index: max_highlighted_word_index.into(),
         current_highlighted_word_index: current_highlighted_word_index,
+        selected_source: _current_text_selection.into(),
+        set_selected_source: Callback::new(move |extracted_by: String| {
+            _source_override.set(Some(extracted_by));
+        }),
+        file_extension: _file_extension_memo.into(),
     });
 
     rsx! {
@@ -160,7 +256,11 @@ fn DocumentPreviewForSearch(
                     border-left: 1px solid rgba(0,0,0,.3);
                 ",
                 // RawMetadataCollector { document_identifier }
-                text_data_viewer::TextDataViewer {}
+                {match *_document_type_memo.read() {
+                    DocumentType::Pdf => rsx! { doc_preview_for_pdf::DocumentPreviewForPdf { document_identifier } },
+                    DocumentType::Code => rsx! { code_data_viewer::CodeDataViewer { document_identifier } },
+                    DocumentType::Text => rsx! { text_data_viewer::TextDataViewer {} },
+                }}
             }
         }
     }
@@ -178,7 +278,21 @@ async fn search_document_text_for_hit_count(document_identifier: DocumentIdentif
 }
 
 #[server]
-async fn search_document_text_for_hits(document_identifier: DocumentIdentifier, find_query: String, extracted_by: String, page_id: u32) -> Result<Vec<DocumentTextSourceHit>, ServerFnError> {
-    let hits = backend::api::documents::search_document_text::search_document_text_for_hits(document_identifier, find_query, extracted_by, page_id).await.map_err(|e| ServerFnError::from(e));
+async fn search_document_text_for_hits(document_identifier: DocumentIdentifier, find_query: String, extracted_by: String, page_id: u32, crop_radius: u32, around: u32, snippet_limit: u32) -> Result<Vec<DocumentTextSourceHit>, ServerFnError> {
+    let hits = backend::api::documents::search_document_text::search_document_text_for_hits(document_identifier, find_query, extracted_by, page_id, crop_radius, around, snippet_limit).await.map_err(|e| ServerFnError::from(e));
     hits
+}
+
+#[server]
+async fn get_file_path_for_syntax_highlight(document_identifier: DocumentIdentifier) -> Result<String, ServerFnError> {
+    backend::api::documents::get_file_path::get_file_path(document_identifier)
+        .await
+        .map_err(|e| ServerFnError::from(e))
+}
+
+#[server]
+async fn get_document_type(document_identifier: DocumentIdentifier) -> Result<DocumentType, ServerFnError> {
+    backend::api::documents::get_code_highlight::get_document_type(document_identifier)
+        .await
+        .map_err(|e| ServerFnError::from(e))
 }
\ No newline at end of file
@@ -2,9 +2,9 @@
 
 use common::search_result::DocumentIdentifier;
 use dioxus::prelude::*;
-use dioxus_free_icons::{Icon, icons::md_navigation_icons::{MdArrowDownward, MdArrowUpward}};
+use dioxus_free_icons::{Icon, icons::md_navigation_icons::{MdArrowDownward, MdArrowDropDown, MdArrowUpward}};
 
-use crate::{components::{document_view_components::doc_preview_for_search::doc_preview_for_text::DocumentViewerResultStore, search_components::search_result_list_controls::NavigationButton}, data_definitions::doc_viewer_state::DocViewerState, pages::search_page::DocViewerStateControl};
+use crate::{components::{document_view_components::doc_preview_for_search::DocumentViewerResultStore, search_components::search_result_list_controls::NavigationButton}, data_definitions::doc_viewer_state::DocViewerState, pages::search_page::DocViewerStateControl};
 
 #[component]
 pub fn PreviewSubtitleBar(document_identifier: ReadSignal<DocumentIdentifier>) -> Element {
@@ -81,6 +81,15 @@ pub fn PreviewSubtitleBar(document_identifier: ReadSignal<DocumentIdentifier>) -
             }
             // SPACER
             div {style:"flex-grow: 1;"}
+            // SNIPPET VIEW TOGGLE
+            div {
+                style: "
+                    flex-grow: 0;
+                    flex-shrink: 0;
+                    display: inline-flex;
+                ",
+                SnippetAroundToggle {}
+            }
             // SOURCE DROP-down
             div {
                 style: "
@@ -88,14 +97,7 @@ pub fn PreviewSubtitleBar(document_identifier: ReadSignal<DocumentIdentifier>) -
                     flex-shrink: 0;
                     display: inline-flex;
                 ",
-                // "Source: ",
-                // div {
-                //     style: "
-                //         border: 1px solid rgba(0, 0, 0, 0.3);
-                //         border-radius: 24px;
-                //     ",
-                //     "Drop-down ▼"
-                // }
+                SourceSelector {}
             }
             // SPACER
             div {style:"flex-grow: 1;"}
@@ -104,6 +106,129 @@ pub fn PreviewSubtitleBar(document_identifier: ReadSignal<DocumentIdentifier>) -
     }
 }
 
+/// Flips the current page's fetch between the whole page (client-cropped to
+/// `crop_radius`) and a focused, server-windowed snippet
+/// (`snippet_around` words either side of each match) by toggling
+/// `DocViewerState::snippet_around` via [`DocViewerState::with_snippet_around_toggled`].
+#[component]
+fn SnippetAroundToggle() -> Element {
+    let state = use_context::<DocViewerStateControl>();
+    let is_focused = use_memo(move || {
+        state.doc_viewer_state.read().as_ref().map(|s| s.snippet_around > 0).unwrap_or(false)
+    });
+
+    rsx! {
+        div {
+            title: "Toggle between the whole page and a focused snippet around each match",
+            style: "
+                display: inline-flex;
+                align-items: center;
+                gap: 4px;
+                border: 1px solid rgba(0, 0, 0, 0.3);
+                border-radius: 24px;
+                padding: 2px 10px;
+                cursor: pointer;
+            ",
+            onclick: move |_| {
+                let current = state.doc_viewer_state.read().clone().unwrap_or_else(|| DocViewerState::from_find_query("".to_string()));
+                state.set_doc_viewer_state.call(current.with_snippet_around_toggled());
+            },
+            if is_focused() { "Focused snippet" } else { "Whole page" }
+        }
+    }
+}
+
+#[component]
+fn SourceSelector() -> Element {
+    let result_store = use_context::<DocumentViewerResultStore>();
+    let all_sources = result_store.all_sources;
+    let selected_source = result_store.selected_source;
+    let set_selected_source = result_store.set_selected_source;
+    let mut is_expanded = use_signal(|| false);
+
+    let selected_label = use_memo(move || {
+        selected_source.read().clone().map(|(extracted_by, _page)| extracted_by).unwrap_or("-".to_string())
+    });
+
+    if all_sources.read().len() < 2 {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            style: "position: relative;",
+            "Source: "
+            div {
+                style: "
+                    display: inline-flex;
+                    align-items: center;
+                    gap: 4px;
+                    border: 1px solid rgba(0, 0, 0, 0.3);
+                    border-radius: 24px;
+                    padding: 2px 10px;
+                    cursor: pointer;
+                ",
+                onclick: move |_e| {
+                    _e.stop_propagation();
+                    *is_expanded.write() ^= true;
+                },
+                "{selected_label}"
+                Icon { icon: MdArrowDropDown, style: "width: 18px; height: 18px;" }
+            }
+            if is_expanded() {
+                div {
+                    style: "
+                        position: fixed;
+                        top: 0px;
+                        left: 0px;
+                        width: 100vw;
+                        height: 100vh;
+                        z-index: 1000;
+                    ",
+                    onclick: move |_e| {
+                        _e.stop_propagation();
+                        *is_expanded.write() = false;
+                    },
+                }
+                div {
+                    style: "
+                        position: absolute;
+                        bottom: 32px;
+                        right: 0px;
+                        min-width: 160px;
+                        background-color: white;
+                        border: 1px solid rgba(0, 0, 0, 0.5);
+                        box-shadow: 0 0 10px 0 rgba(0, 0, 0, 0.5);
+                        border-radius: 4px;
+                        padding: 5px;
+                        gap: 2px;
+                        z-index: 1001;
+                        display: flex;
+                        flex-direction: column;
+                    ",
+                    for source in all_sources.read().clone() {
+                        div {
+                            key: "{source.extracted_by}",
+                            style: "
+                                padding: 4px 10px;
+                                cursor: pointer;
+                                border-radius: 4px;
+                            ",
+                            class: "hoover4-hover-shadow-background",
+                            onclick: move |_e| {
+                                _e.stop_propagation();
+                                set_selected_source.call(source.extracted_by.clone());
+                                *is_expanded.write() = false;
+                            },
+                            "{source.extracted_by}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn SearchHitSelector() -> Element {
     let max_highlighted_word_index = use_context::<DocumentViewerResultStore>().max_highlighted_word_index;
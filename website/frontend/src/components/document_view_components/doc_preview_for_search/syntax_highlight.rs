@@ -0,0 +1,151 @@
+//! Language-agnostic syntax token classifier driven by file extension.
+//!
+//! This is not a real per-language grammar: it's a small lexer shared across
+//! languages that recognizes comments, strings, numbers and a per-language
+//! keyword set, so the document preview can color-code source code without a
+//! dependency on a full syntax-highlighting crate.
+
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxTokenClass {
+    Plain,
+    Comment,
+    String,
+    Number,
+    Keyword,
+    Identifier,
+    Punctuation,
+}
+
+struct LanguageRules {
+    line_comment: Option<&'static str>,
+    block_comment: Option<(&'static str, &'static str)>,
+    string_delims: &'static [char],
+    keywords: &'static [&'static str],
+}
+
+const RUST_KEYWORDS: &[&str] = &["fn", "let", "mut", "pub", "use", "struct", "enum", "impl", "trait", "for", "in", "while", "loop", "if", "else", "match", "return", "break", "continue", "true", "false", "self", "Self", "mod", "crate", "as", "const", "static", "async", "await", "move", "ref", "where", "unsafe", "type", "dyn", "extern", "super"];
+const PYTHON_KEYWORDS: &[&str] = &["def", "class", "import", "from", "as", "if", "elif", "else", "for", "while", "return", "yield", "try", "except", "finally", "with", "lambda", "None", "True", "False", "pass", "break", "continue", "global", "nonlocal", "assert", "del", "raise", "not", "and", "or", "in", "is", "async", "await"];
+const JS_KEYWORDS: &[&str] = &["function", "const", "let", "var", "if", "else", "for", "while", "return", "class", "extends", "new", "this", "typeof", "instanceof", "import", "export", "from", "as", "async", "await", "try", "catch", "finally", "throw", "switch", "case", "default", "break", "continue", "null", "undefined", "true", "false", "of", "in", "yield", "delete", "void"];
+const GO_KEYWORDS: &[&str] = &["func", "package", "import", "var", "const", "type", "struct", "interface", "map", "chan", "go", "defer", "select", "case", "switch", "if", "else", "for", "range", "return", "break", "continue", "default", "fallthrough", "nil", "true", "false"];
+const C_LIKE_KEYWORDS: &[&str] = &["int", "char", "float", "double", "void", "if", "else", "for", "while", "do", "switch", "case", "default", "break", "continue", "return", "struct", "class", "public", "private", "protected", "static", "final", "const", "new", "this", "true", "false", "null", "enum", "namespace", "template", "virtual", "override", "typedef", "include", "define"];
+
+fn rules_for_extension(extension: &str) -> Option<LanguageRules> {
+    match extension.to_ascii_lowercase().as_str() {
+        "rs" => Some(LanguageRules { line_comment: Some("//"), block_comment: Some(("/*", "*/")), string_delims: &['"'], keywords: RUST_KEYWORDS }),
+        "py" => Some(LanguageRules { line_comment: Some("#"), block_comment: None, string_delims: &['"', '\''], keywords: PYTHON_KEYWORDS }),
+        "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" => Some(LanguageRules { line_comment: Some("//"), block_comment: Some(("/*", "*/")), string_delims: &['"', '\'', '`'], keywords: JS_KEYWORDS }),
+        "go" => Some(LanguageRules { line_comment: Some("//"), block_comment: Some(("/*", "*/")), string_delims: &['"', '`'], keywords: GO_KEYWORDS }),
+        "c" | "h" | "cpp" | "hpp" | "cc" | "cxx" | "java" => Some(LanguageRules { line_comment: Some("//"), block_comment: Some(("/*", "*/")), string_delims: &['"', '\''], keywords: C_LIKE_KEYWORDS }),
+        _ => None,
+    }
+}
+
+/// Classifies `text` into non-overlapping, sorted `(start, end, class)` char-offset
+/// ranges for the language implied by `extension`. Returns an empty vector for
+/// unknown or missing extensions, leaving the text to render as plain.
+pub fn tokenize_ranges(text: &str, extension: Option<&str>) -> Vec<(usize, usize, SyntaxTokenClass)> {
+    let Some(extension) = extension else { return Vec::new() };
+    let Some(rules) = rules_for_extension(extension) else { return Vec::new() };
+    let keywords: HashSet<&str> = rules.keywords.iter().copied().collect();
+
+    let chars: Vec<char> = text.chars().collect();
+    let len = chars.len();
+    let mut ranges = Vec::new();
+    let mut pos = 0usize;
+
+    let starts_with = |pos: usize, needle: &str| -> bool {
+        let needle_chars: Vec<char> = needle.chars().collect();
+        if pos + needle_chars.len() > len {
+            return false;
+        }
+        chars[pos..pos + needle_chars.len()] == needle_chars[..]
+    };
+
+    while pos < len {
+        let start = pos;
+
+        if let Some(line_comment) = rules.line_comment {
+            if starts_with(pos, line_comment) {
+                while pos < len && chars[pos] != '\n' {
+                    pos += 1;
+                }
+                ranges.push((start, pos, SyntaxTokenClass::Comment));
+                continue;
+            }
+        }
+
+        if let Some((open, close)) = rules.block_comment {
+            if starts_with(pos, open) {
+                pos += open.chars().count();
+                while pos < len && !starts_with(pos, close) {
+                    pos += 1;
+                }
+                pos = (pos + close.chars().count()).min(len);
+                ranges.push((start, pos, SyntaxTokenClass::Comment));
+                continue;
+            }
+        }
+
+        if rules.string_delims.contains(&chars[pos]) {
+            let delim = chars[pos];
+            pos += 1;
+            while pos < len && chars[pos] != delim {
+                if chars[pos] == '\\' && pos + 1 < len {
+                    pos += 2;
+                } else {
+                    pos += 1;
+                }
+            }
+            pos = (pos + 1).min(len);
+            ranges.push((start, pos, SyntaxTokenClass::String));
+            continue;
+        }
+
+        if chars[pos].is_ascii_digit() {
+            while pos < len && (chars[pos].is_ascii_digit() || chars[pos] == '.' || chars[pos] == '_') {
+                pos += 1;
+            }
+            ranges.push((start, pos, SyntaxTokenClass::Number));
+            continue;
+        }
+
+        if chars[pos].is_alphabetic() || chars[pos] == '_' {
+            while pos < len && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            let word: String = chars[start..pos].iter().collect();
+            let class = if keywords.contains(word.as_str()) { SyntaxTokenClass::Keyword } else { SyntaxTokenClass::Identifier };
+            ranges.push((start, pos, class));
+            continue;
+        }
+
+        if chars[pos].is_whitespace() {
+            while pos < len && chars[pos].is_whitespace() {
+                pos += 1;
+            }
+            ranges.push((start, pos, SyntaxTokenClass::Plain));
+            continue;
+        }
+
+        pos += 1;
+        ranges.push((start, pos, SyntaxTokenClass::Punctuation));
+    }
+
+    ranges
+}
+
+/// Hex color for a token class, or `None` for classes that should keep the
+/// surrounding text color (plain text and identifiers).
+pub fn class_color(class: SyntaxTokenClass) -> Option<&'static str> {
+    match class {
+        SyntaxTokenClass::Plain => None,
+        SyntaxTokenClass::Comment => Some("#6A737D"),
+        SyntaxTokenClass::String => Some("#032F62"),
+        SyntaxTokenClass::Number => Some("#005CC5"),
+        SyntaxTokenClass::Keyword => Some("#D73A49"),
+        SyntaxTokenClass::Identifier => None,
+        SyntaxTokenClass::Punctuation => None,
+    }
+}
@@ -1,12 +1,13 @@
 //! Document preview text viewer component.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
+use common::text_highlight::HighlightTextSpan;
 use dioxus::prelude::*;
 
-use crate::{components::suspend_boundary::LoadingIndicator, pages::search_page::DocViewerStateControl};
+use crate::{components::{document_view_components::math_render::{KATEX_CSS, KATEX_JS}, search_components::search_result_item_card::highlight_color, suspend_boundary::LoadingIndicator}, pages::search_page::DocViewerStateControl};
 
-use super::DocumentViewerResultStore;
+use super::{math_detect::find_math_ranges, syntax_highlight::{class_color, tokenize_ranges, SyntaxTokenClass}, DocumentViewerResultStore};
 
 #[component]
 pub fn TextDataViewer() -> Element {
@@ -28,13 +29,87 @@ pub fn TextDataViewer() -> Element {
         }
     });
     rsx! {
-        TextDataInner { mounts }
+        div {
+            style: "
+                display: flex;
+                flex-direction: row;
+                height: 100%;
+                width: 100%;
+            ",
+            div { style: "flex: 1; min-width: 0; height: 100%;", TextDataInner { mounts } }
+            HitMapOverlay {}
+        }
+    }
+}
+
+/// Thin vertical strip alongside the scroll container with one tick per
+/// highlighted span, positioned proportionally over
+/// `max_highlighted_word_index` so investigators can see at a glance where
+/// hits cluster in a long document. Clicking a tick jumps straight there.
+#[component]
+fn HitMapOverlay() -> Element {
+    let current_text_data = use_context::<DocumentViewerResultStore>().current_text_data;
+    let max_highlighted_word_index = use_context::<DocumentViewerResultStore>().max_highlighted_word_index;
+    let current_highlighted_word_index = use_context::<DocumentViewerResultStore>().current_highlighted_word_index;
+
+    let hit_indices: Vec<u64> = match current_text_data.read().clone() {
+        Some(Ok(text_data)) if !text_data.is_empty() => {
+            text_data[0].highlight_text_spans.iter()
+                .filter(|span| span.is_highlighted)
+                .map(|span| span.index)
+                .collect()
+        }
+        _ => vec![],
+    };
+
+    let max_index = *max_highlighted_word_index.read();
+    if hit_indices.is_empty() || max_index == 0 {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            style: "
+                position: relative;
+                width: 12px;
+                height: 100%;
+                flex-shrink: 0;
+                background-color: rgba(0, 0, 0, 0.04);
+                border-left: 1px solid rgba(0, 0, 0, 0.1);
+            ",
+            for index in hit_indices {
+                HitMapTick { index, max_index, current_highlighted_word_index }
+            }
+        }
+    }
+}
+
+#[component]
+fn HitMapTick(index: u64, max_index: u32, mut current_highlighted_word_index: Signal<u32>) -> Element {
+    let top_percent = index as f64 / max_index as f64 * 100.0;
+    let is_current = index as u32 == *current_highlighted_word_index.read();
+    let background_color = if is_current { "#eb3f00" } else { "#eb3f0099" };
+
+    rsx! {
+        div {
+            style: "
+                position: absolute;
+                left: 2px;
+                right: 2px;
+                top: {top_percent}%;
+                height: {if is_current { \"3px\" } else { \"2px\" }};
+                background-color: {background_color};
+                cursor: pointer;
+            ",
+            onclick: move |_| current_highlighted_word_index.set(index as u32),
+        }
     }
 }
 
 #[component]
 fn TextDataInner(mut mounts: Signal<BTreeMap<u32, Event<MountedData>>>) -> Element {
     let current_text_data = use_context::<DocumentViewerResultStore>().current_text_data;
+    let file_extension = use_context::<DocumentViewerResultStore>().file_extension;
     let current_query = use_context::<DocViewerStateControl>().doc_viewer_state.read().as_ref().map(|state| state.find_query.clone()).unwrap_or("".to_string());
     let text_data= match current_text_data.read().clone() {
         Some(Ok(text_data)) => {
@@ -65,14 +140,18 @@ fn TextDataInner(mut mounts: Signal<BTreeMap<u32, Event<MountedData>>>) -> Eleme
         }
     };
 
-    let spans = text_data.highlight_text_spans.iter().map(|i| {
-        let i = i.clone();
-        let index = i.index as u32;
+    let merged_spans = merge_with_syntax(&text_data.highlight_text_spans, file_extension.read().as_deref());
+    let spans = merged_spans.into_iter().map(|span| {
+        if let Some((tex_source, is_block)) = span.tex_source {
+            return rsx! { TextMathSpan { tex_source, is_block } };
+        }
+        let index = span.index as u32;
+        let syntax_color = class_color(span.class);
         rsx! {
-            if i.is_highlighted {
-                TextDataSpan { mounts, index, text: i.text }
+            if span.is_highlighted {
+                TextDataSpan { mounts, index, text: span.text, register_mount: span.mount_anchor, syntax_color, term_index: span.term_index }
             } else {
-                TextDataSpanClean { text: i.text }
+                TextDataSpanClean { text: span.text, syntax_color }
             }
         }
     }).collect::<Vec<_>>();
@@ -100,32 +179,170 @@ fn TextDataInner(mut mounts: Signal<BTreeMap<u32, Event<MountedData>>>) -> Eleme
 }
 
 #[component]
-fn TextDataSpan(mounts: Signal<BTreeMap<u32, Event<MountedData>>>, index: u32,  text: String) -> Element {
+fn TextDataSpan(mounts: Signal<BTreeMap<u32, Event<MountedData>>>, index: u32, text: String, register_mount: bool, syntax_color: Option<&'static str>, term_index: Option<usize>) -> Element {
     let current_highlighted_word_index = use_context::<DocumentViewerResultStore>().current_highlighted_word_index;
-    let color = use_memo(move || {
+    let border_color = use_memo(move || {
         if index == *current_highlighted_word_index.read() as u32 {
             return "black";
         }
         return "transparent";
     });
+    let text_color = syntax_color.unwrap_or("rgb(0, 0, 0)");
+    let background_color = highlight_color(term_index);
 
     rsx! {
         span {
             onmounted:  move |event| async move {
-                mounts.write().insert(index, event.clone());
+                if register_mount {
+                    mounts.write().insert(index, event.clone());
+                }
             },
-            style: "background-color: #eb3f004d; color: rgb(0, 0, 0); white-space:pre-wrap; word-wrap: break-word; border: 2px dotted {color};",
+            style: "background-color: {background_color}; color: {text_color}; white-space:pre-wrap; word-wrap: break-word; border: 2px dotted {border_color};",
             "{text}"
         }
     }
 }
 
 #[component]
-fn TextDataSpanClean(text: String) -> Element {
+fn TextDataSpanClean(text: String, syntax_color: Option<&'static str>) -> Element {
+    let text_color = syntax_color.unwrap_or("rgb(0, 0, 0)");
     rsx! {
         span {
-            style: "color: rgb(0, 0, 0); white-space:pre-wrap; word-wrap: break-word;",
+            style: "color: {text_color}; white-space:pre-wrap; word-wrap: break-word;",
             "{text}"
         }
     }
+}
+
+/// Opt-in math typesetting for a `$...$`/`$$...$$` span found by
+/// `find_math_ranges`, rendered client-side via KaTeX (loaded once into the
+/// page on first use) so scientific/legal documents show formatted math
+/// instead of raw TeX source. Falls back to the literal TeX text until the
+/// render completes (or if it fails).
+#[component]
+fn TextMathSpan(tex_source: String, is_block: bool) -> Element {
+    let mut rendered_html = use_signal(|| None::<String>);
+    use_effect(move || {
+        let tex_source = tex_source.clone();
+        spawn(async move {
+            let tex_json = serde_json::to_string(&tex_source).unwrap_or_else(|_| "\"\"".to_string());
+            let script = format!(
+                r#"
+                (async () => {{
+                    if (!window.__hoover4KatexLoaded) {{
+                        await new Promise((resolve, reject) => {{
+                            const link = document.createElement('link');
+                            link.rel = 'stylesheet';
+                            link.href = '{KATEX_CSS}';
+                            document.head.appendChild(link);
+                            const script = document.createElement('script');
+                            script.src = '{KATEX_JS}';
+                            script.onload = resolve;
+                            script.onerror = reject;
+                            document.head.appendChild(script);
+                        }});
+                        window.__hoover4KatexLoaded = true;
+                    }}
+                    try {{
+                        dioxus.send(katex.renderToString({tex_json}, {{ displayMode: {is_block}, throwOnError: false }}));
+                    }} catch (e) {{
+                        dioxus.send(null);
+                    }}
+                }})();
+                "#
+            );
+            let mut eval = document::eval(&script);
+            if let Ok(Some(html)) = eval.recv::<Option<String>>().await {
+                rendered_html.set(Some(html));
+            }
+        });
+    });
+
+    match rendered_html() {
+        Some(html) => rsx! { span { dangerous_inner_html: "{html}" } },
+        None => rsx! { span { style: "color: rgba(0, 0, 0, 0.45);", "{tex_source}" } },
+    }
+}
+
+/// A contiguous run of text that carries at most one syntax class plus an
+/// optional search-hit highlight, produced by splitting the original
+/// highlight spans and the syntax token ranges at all shared boundaries.
+struct MergedSpan {
+    text: String,
+    is_highlighted: bool,
+    index: u64,
+    term_index: Option<usize>,
+    class: SyntaxTokenClass,
+    /// True for the first fragment of an original highlighted span, so only
+    /// one `onmounted` registration happens per search hit.
+    mount_anchor: bool,
+    /// Set to this span's TeX source and block/inline-ness when it's an
+    /// exact `$...$`/`$$...$$` match from `find_math_ranges`, so it renders
+    /// via `TextMathSpan` instead of as plain/highlighted text. Left `None`
+    /// (falling back to plain text) on the rare case where a search hit
+    /// highlight boundary falls inside the formula, splitting it.
+    tex_source: Option<(String, bool)>,
+}
+
+fn merge_with_syntax(spans: &[HighlightTextSpan], extension: Option<&str>) -> Vec<MergedSpan> {
+    let mut full_text = String::new();
+    let mut span_ranges: Vec<(usize, usize, bool, u64, Option<usize>)> = Vec::new();
+    let mut offset = 0usize;
+    for span in spans {
+        let len = span.text.chars().count();
+        span_ranges.push((offset, offset + len, span.is_highlighted, span.index, span.term_index));
+        full_text.push_str(&span.text);
+        offset += len;
+    }
+
+    let syntax_ranges = tokenize_ranges(&full_text, extension);
+    // Math only makes sense for prose (scientific/legal extracted text), not
+    // source files, where a bare `$` is far more likely to be a shell
+    // variable or regex anchor than the start of a formula.
+    let math_ranges = if extension.is_none() { find_math_ranges(&full_text) } else { Vec::new() };
+    let chars: Vec<char> = full_text.chars().collect();
+
+    let mut breakpoints: BTreeSet<usize> = BTreeSet::new();
+    breakpoints.insert(0);
+    breakpoints.insert(chars.len());
+    for (start, end, _, _, _) in &span_ranges {
+        breakpoints.insert(*start);
+        breakpoints.insert(*end);
+    }
+    for (start, end, _) in &syntax_ranges {
+        breakpoints.insert(*start);
+        breakpoints.insert(*end);
+    }
+    for math_range in &math_ranges {
+        breakpoints.insert(math_range.start);
+        breakpoints.insert(math_range.end);
+    }
+    let breakpoints: Vec<usize> = breakpoints.into_iter().collect();
+
+    let mut merged = Vec::new();
+    for window in breakpoints.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        if start >= end {
+            continue;
+        }
+        let Some(&(span_start, _, is_highlighted, index, term_index)) = span_ranges.iter().find(|(s, e, _, _, _)| start >= *s && start < *e) else { continue };
+        let class = syntax_ranges.iter()
+            .find(|(s, e, _)| start >= *s && start < *e)
+            .map(|(_, _, class)| *class)
+            .unwrap_or(SyntaxTokenClass::Plain);
+        let tex_source = math_ranges.iter()
+            .find(|m| m.start == start && m.end == end)
+            .map(|m| (m.tex_source.clone(), m.is_block));
+        let text: String = chars[start..end].iter().collect();
+        merged.push(MergedSpan {
+            text,
+            is_highlighted,
+            index,
+            term_index,
+            class,
+            mount_anchor: is_highlighted && start == span_start,
+            tex_source,
+        });
+    }
+    merged
 }
\ No newline at end of file
@@ -2,10 +2,10 @@
 
 use dioxus::prelude::*;
 
-use common::search_result::DocumentIdentifier;
-use dioxus_free_icons::{Icon, icons::{go_icons::GoDatabase, md_editor_icons::MdInsertDriveFile}};
+use common::search_result::{DocumentIdentifier, SearchResultDocumentItem};
+use dioxus_free_icons::{Icon, icons::{go_icons::GoDatabase, md_action_icons::MdSearch, md_editor_icons::MdInsertDriveFile}};
 
-use crate::components::search_components::card_action_buttons::{DocCardActionButtonMore, DocCardActionButtonOpenNewTab};
+use crate::{api::search_api::search_similar, components::search_components::card_action_buttons::{DocCardActionButtonMore, DocCardActionButtonOpenNewTab}, routes::Route};
 
 #[component]
 pub fn DocTitleBar(document_identifier: ReadSignal<DocumentIdentifier>) -> Element {
@@ -40,6 +40,7 @@ pub fn DocTitleBar(document_identifier: ReadSignal<DocumentIdentifier>) -> Eleme
                     justify-content: center;
                 ",
                 DocCardActionButtonOpenNewTab {document_identifier: document_identifier()}
+                MoreLikeThisButton {document_identifier: document_identifier()}
                 DocCardActionButtonMore {document_identifier: document_identifier()}
             }
 
@@ -47,6 +48,115 @@ pub fn DocTitleBar(document_identifier: ReadSignal<DocumentIdentifier>) -> Eleme
     }
 }
 
+/// "More like this" affordance: a popover listing documents ranked by
+/// embedding similarity to the one currently open, via
+/// [`search_similar`](crate::api::search_api::search_similar). Click a
+/// result to jump straight to it.
+#[component]
+fn MoreLikeThisButton(document_identifier: ReadSignal<DocumentIdentifier>) -> Element {
+    let mut is_expanded = use_signal(|| false);
+    let similar_res = use_resource(move || {
+        let document_identifier = document_identifier.read().clone();
+        async move { search_similar(document_identifier, 8).await }
+    });
+
+    rsx! {
+        div {
+            style: "position: relative;",
+            button {
+                title: "More like this",
+                style: "
+                    width: 40px;
+                    height: 40px;
+                    cursor: pointer;
+                    border: 1px solid #000;
+                    border-radius: 8px;
+                    background: white;
+                    color: black;
+                    display: flex;
+                    align-items: center;
+                    justify-content: center;
+                    padding: 1px;
+                    margin: 1px;
+                ",
+                class: "hoover4-hover-shadow-background",
+                onclick: move |e| {
+                    e.stop_propagation();
+                    *is_expanded.write() ^= true;
+                },
+                Icon { icon: MdSearch, style: "width: 22px; height: 22px;" }
+            }
+            if is_expanded() {
+                div {
+                    style: "
+                        position: fixed;
+                        top: 0px;
+                        left: 0px;
+                        width: 100vw;
+                        height: 100vh;
+                        z-index: 1000;
+                    ",
+                    onclick: move |_| { is_expanded.set(false); },
+                }
+                div {
+                    style: "
+                        position: absolute;
+                        top: calc(100% + 6px);
+                        right: 0px;
+                        width: 340px;
+                        max-height: 400px;
+                        overflow-y: auto;
+                        background-color: white;
+                        border: 1px solid rgba(0, 0, 0, 0.5);
+                        box-shadow: 0 0 10px 0 rgba(0, 0, 0, 0.5);
+                        border-radius: 8px;
+                        padding: 8px;
+                        z-index: 1001;
+                    ",
+                    onclick: move |e| e.stop_propagation(),
+                    div { style: "font-size: 15px; font-weight: 500; padding: 4px 6px 8px 6px;", "More like this" }
+                    match similar_res.read().as_ref() {
+                        None => rsx! { div { style: "padding: 6px; color: rgba(0,0,0,0.5);", "Loading…" } },
+                        Some(Err(e)) => rsx! { div { style: "padding: 6px; color: #DC2626;", "! error: {e}" } },
+                        Some(Ok(items)) if items.is_empty() => rsx! {
+                            div { style: "padding: 6px; color: rgba(0,0,0,0.5);", "No similar documents found." }
+                        },
+                        Some(Ok(items)) => rsx! {
+                            for item in items.clone() {
+                                MoreLikeThisRow { key: "{item.collection_dataset}-{item.file_hash}", item, on_navigate: move |_| is_expanded.set(false) }
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn MoreLikeThisRow(item: SearchResultDocumentItem, on_navigate: Callback<()>) -> Element {
+    let document_identifier = item.document_identifier();
+    rsx! {
+        Link {
+            to: Route::view_document_page(document_identifier),
+            style: "
+                display: block;
+                padding: 6px;
+                border-radius: 6px;
+                font-size: 14px;
+                color: #111827;
+                text-decoration: none;
+                overflow: hidden;
+                text-overflow: ellipsis;
+                white-space: nowrap;
+            ",
+            class: "hoover4-hover-shadow-background",
+            onclick: move |_| on_navigate.call(()),
+            "{item.title}"
+        }
+    }
+}
+
 #[component]
 fn CollectionAndFilenameSection(document_identifier: ReadSignal<DocumentIdentifier>) -> Element {
     let collection_dataset = document_identifier.read().clone().collection_dataset;
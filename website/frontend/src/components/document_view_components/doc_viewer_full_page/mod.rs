@@ -3,11 +3,31 @@
 use common::search_result::DocumentIdentifier;
 use dioxus::prelude::*;
 
-use crate::components::document_view_components::{doc_title_bar::DocTitleBar, raw_metadata_collector::RawMetadataCollector};
+use crate::{
+    components::{
+        document_view_components::{doc_preview_for_search::DocumentPreviewForSearch, doc_title_bar::DocTitleBar, raw_metadata_collector::RawMetadataCollector},
+        suspend_boundary::SuspendWrapper,
+    },
+    data_definitions::doc_viewer_state::DocViewerState,
+    pages::search_page::DocViewerStateControl,
+    routes::Route,
+};
 
 
 #[component]
-pub fn DocViewerRoot(document_identifier: ReadSignal<DocumentIdentifier>) -> Element {
+pub fn DocViewerRoot(document_identifier: ReadSignal<DocumentIdentifier>, doc_viewer_state: ReadSignal<Option<DocViewerState>>) -> Element {
+    use_context_provider(move || DocViewerStateControl {
+        doc_viewer_state,
+        set_doc_viewer_state: Callback::new(move |state: DocViewerState| {
+            navigator().replace(Route::view_document_page_at_match(document_identifier.read().clone(), state));
+        }),
+    });
+
+    // Only deep-links and the in-page "search in document" box carry a find
+    // query; opening a document with no search context falls back to the
+    // plain raw-metadata browser this page always showed.
+    let has_find_query = doc_viewer_state.read().as_ref().map(|state| !state.find_query.trim().is_empty()).unwrap_or(false);
+
     rsx! {
         div {
             style: "
@@ -17,10 +37,14 @@ pub fn DocViewerRoot(document_identifier: ReadSignal<DocumentIdentifier>) -> Ele
                 width: 100%;
                 overflow: hidden;
             ",
-            DocTitleBar { document_identifier }
-            div {
-                style: "width: 100%; height: calc(100% - 54px); flex-grow: 0; flex-shrink: 0;",
-                RawMetadataCollector {  document_identifier }
+            if has_find_query {
+                SuspendWrapper { DocumentPreviewForSearch { document_identifier } }
+            } else {
+                DocTitleBar { document_identifier }
+                div {
+                    style: "width: 100%; height: calc(100% - 54px); flex-grow: 0; flex-shrink: 0;",
+                    RawMetadataCollector {  document_identifier }
+                }
             }
         }
     }
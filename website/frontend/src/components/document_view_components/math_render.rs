@@ -0,0 +1,29 @@
+//! Shared KaTeX asset handles for rendering LaTeX math (`$...$`/`$$...$$`)
+//! detected in document content, used by both the PDF page viewer (typeset
+//! inside its own isolated `iframe` document) and the plain-text viewer
+//! (typeset directly in the app's DOM via `document::eval`).
+
+use dioxus::prelude::*;
+
+pub const KATEX_CSS: Asset = asset!("/assets/katex/katex.min.css");
+pub const KATEX_JS: Asset = asset!("/assets/katex/katex.min.js");
+
+/// A `<link>` + `<script>` pair that loads KaTeX inside an `iframe`'s own
+/// `srcdoc` document, followed by a typeset pass over every
+/// `.hoover4-math` placeholder span left by the backend's
+/// `inject_math_placeholders` scan.
+pub fn katex_iframe_script() -> String {
+    format!(
+        r#"<link rel="stylesheet" href="{KATEX_CSS}">
+<script src="{KATEX_JS}"></script>
+<script>
+  document.querySelectorAll('.hoover4-math').forEach(function (el) {{
+    try {{
+      katex.render(el.dataset.tex, el, {{ displayMode: el.dataset.display === 'true', throwOnError: false }});
+    }} catch (e) {{
+      console.error('KaTeX render failed', e);
+    }}
+  }});
+</script>"#
+    )
+}
@@ -0,0 +1,128 @@
+//! Left-hand collapsible tree of collections and their virtual directory
+//! hierarchy, derived from `vfs_files` paths.
+
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::md_file_icons::MdFolder, icons::md_navigation_icons::MdArrowDropDown};
+
+use crate::{api::file_browser_api::{list_collections, list_directory_entries}, components::{error_boundary::ComponentErrorDisplay, file_browser_components::file_browser_nav_state::FileBrowserNavState, suspend_boundary::LoadingIndicator}};
+
+#[component]
+pub fn CollectionTree() -> Element {
+    let collections = use_resource(move || list_collections());
+
+    let collections = match collections.read().clone() {
+        Some(Ok(collections)) => collections,
+        Some(Err(e)) => return rsx! { ComponentErrorDisplay { error_txt: format!("{:#?}", e) } },
+        None => return rsx! { LoadingIndicator {} },
+    };
+
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                flex-direction: column;
+                padding: 8px 0;
+            ",
+            for collection in collections {
+                div {
+                    key: "{collection}",
+                    DirectoryTreeNode {
+                        collection_dataset: collection,
+                        path_segments: vec![],
+                        depth: 0,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DirectoryTreeNode(collection_dataset: String, path_segments: Vec<String>, depth: u32) -> Element {
+    let nav_state = use_context::<FileBrowserNavState>();
+    let mut is_expanded = use_signal(|| false);
+
+    let name = path_segments.last().cloned().unwrap_or_else(|| collection_dataset.clone());
+    let is_current = use_memo({
+        let collection_dataset = collection_dataset.clone();
+        let path_segments = path_segments.clone();
+        move || {
+            nav_state.current_collection.read().as_deref() == Some(collection_dataset.as_str())
+                && *nav_state.current_path_segments.read() == path_segments
+        }
+    });
+
+    let indent = 14 + depth * 16;
+    let background_color = use_memo(move || if is_current() { "#4096FF33" } else { "transparent" });
+
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                align-items: center;
+                gap: 2px;
+                padding: 4px 8px 4px {indent}px;
+                cursor: pointer;
+                font-size: 14px;
+                background-color: {background_color};
+            ",
+            class: "hoover4-hover-shadow-background",
+            onclick: {
+                let collection_dataset = collection_dataset.clone();
+                let path_segments = path_segments.clone();
+                move |_| {
+                    *is_expanded.write() = true;
+                    nav_state.open_directory.call((collection_dataset.clone(), path_segments.clone()));
+                }
+            },
+            div {
+                style: "width: 18px; height: 18px; display: flex; align-items: center; justify-content: center; flex-shrink: 0;",
+                onclick: move |e| {
+                    e.stop_propagation();
+                    *is_expanded.write() ^= true;
+                },
+                Icon {
+                    icon: MdArrowDropDown,
+                    style: if is_expanded() { "width: 16px; height: 16px; transform: rotate(0deg);" } else { "width: 16px; height: 16px; transform: rotate(-90deg);" },
+                }
+            }
+            Icon { icon: MdFolder, style: "width: 16px; height: 16px; color: rgba(0, 0, 0, 0.6); flex-shrink: 0;" }
+            span {
+                style: "overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
+                "{name}"
+            }
+        }
+        if is_expanded() {
+            DirectoryTreeChildren { collection_dataset, path_segments, depth }
+        }
+    }
+}
+
+#[component]
+fn DirectoryTreeChildren(collection_dataset: String, path_segments: Vec<String>, depth: u32) -> Element {
+    let path_prefix = path_segments.join("/");
+    let children = use_resource({
+        let collection_dataset = collection_dataset.clone();
+        let path_prefix = path_prefix.clone();
+        move || list_directory_entries(collection_dataset.clone(), path_prefix.clone())
+    });
+
+    let children = match children.read().clone() {
+        Some(Ok(entries)) => entries,
+        Some(Err(e)) => return rsx! { ComponentErrorDisplay { error_txt: format!("{:#?}", e) } },
+        None => return rsx! { LoadingIndicator {} },
+    };
+
+    rsx! {
+        for entry in children.into_iter().filter(|e| e.is_directory) {
+            div {
+                key: "{entry.path}",
+                DirectoryTreeNode {
+                    collection_dataset: collection_dataset.clone(),
+                    path_segments: entry.path.split('/').map(|s| s.to_string()).collect::<Vec<_>>(),
+                    depth: depth + 1,
+                }
+            }
+        }
+    }
+}
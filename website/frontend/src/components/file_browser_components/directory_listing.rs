@@ -0,0 +1,203 @@
+//! Main pane of the file browser: a breadcrumb trail for the current
+//! directory, followed by a listing of its immediate entries with
+//! file-type icons, sizes and hashes.
+
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::{md_editor_icons::MdInsertDriveFile, md_file_icons::MdFolder}};
+
+use crate::{api::file_browser_api::list_directory_entries, components::{error_boundary::ComponentErrorDisplay, file_browser_components::file_browser_nav_state::FileBrowserNavState, suspend_boundary::LoadingIndicator}};
+
+fn format_size_bytes(size_bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = size_bytes as f64;
+    let mut unit_index = 0;
+    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit_index += 1;
+    }
+    if unit_index == 0 {
+        format!("{size_bytes} {}", UNITS[unit_index])
+    } else {
+        format!("{size:.1} {}", UNITS[unit_index])
+    }
+}
+
+#[component]
+pub fn DirectoryListing() -> Element {
+    let nav_state = use_context::<FileBrowserNavState>();
+
+    let Some(collection_dataset) = nav_state.current_collection.read().clone() else {
+        return rsx! {
+            div {
+                style: "padding: 24px; color: rgba(0, 0, 0, 0.5); font-size: 16px;",
+                "Select a collection on the left to browse its files."
+            }
+        };
+    };
+
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                flex-direction: column;
+                height: 100%;
+                width: 100%;
+            ",
+            Breadcrumbs { collection_dataset: collection_dataset.clone() }
+            DirectoryListingEntries { collection_dataset }
+        }
+    }
+}
+
+#[component]
+fn Breadcrumbs(collection_dataset: String) -> Element {
+    let nav_state = use_context::<FileBrowserNavState>();
+    let path_segments = nav_state.current_path_segments.read().clone();
+
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                flex-direction: row;
+                align-items: center;
+                flex-wrap: wrap;
+                gap: 4px;
+                padding: 10px 16px;
+                border-bottom: 1px solid rgba(0, 0, 0, 0.2);
+                font-size: 14px;
+                flex-shrink: 0;
+            ",
+            span {
+                class: "hoover4-hover-shadow-background",
+                style: "cursor: pointer; padding: 2px 4px; border-radius: 4px; font-weight: 600;",
+                onclick: {
+                    let collection_dataset = collection_dataset.clone();
+                    move |_| nav_state.open_directory.call((collection_dataset.clone(), vec![]))
+                },
+                "{collection_dataset}"
+            }
+            for (index , segment) in path_segments.iter().enumerate() {
+                span { style: "color: rgba(0, 0, 0, 0.4);", "/" }
+                span {
+                    key: "{index}",
+                    class: "hoover4-hover-shadow-background",
+                    style: "cursor: pointer; padding: 2px 4px; border-radius: 4px;",
+                    onclick: {
+                        let collection_dataset = collection_dataset.clone();
+                        let crumb_path = path_segments[..=index].to_vec();
+                        move |_| nav_state.open_directory.call((collection_dataset.clone(), crumb_path.clone()))
+                    },
+                    "{segment}"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DirectoryListingEntries(collection_dataset: String) -> Element {
+    let nav_state = use_context::<FileBrowserNavState>();
+    let path_prefix = nav_state.current_path_prefix();
+
+    let entries = use_resource({
+        let collection_dataset = collection_dataset.clone();
+        move || list_directory_entries(collection_dataset.clone(), path_prefix.clone())
+    });
+
+    let entries = match entries.read().clone() {
+        Some(Ok(entries)) => entries,
+        Some(Err(e)) => return rsx! { ComponentErrorDisplay { error_txt: format!("{:#?}", e) } },
+        None => return rsx! { LoadingIndicator {} },
+    };
+
+    if entries.is_empty() {
+        return rsx! {
+            div {
+                style: "padding: 24px; color: rgba(0, 0, 0, 0.5); font-size: 16px;",
+                "This directory is empty."
+            }
+        };
+    }
+
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                flex-direction: column;
+                overflow-y: auto;
+                flex: 1;
+            ",
+            div {
+                style: "
+                    display: flex;
+                    flex-direction: row;
+                    padding: 8px 16px;
+                    font-size: 12px;
+                    font-weight: 600;
+                    color: rgba(0, 0, 0, 0.5);
+                    border-bottom: 1px solid rgba(0, 0, 0, 0.1);
+                ",
+                span { style: "flex: 1; min-width: 0;", "Name" }
+                span { style: "width: 100px; flex-shrink: 0;", "Size" }
+                span { style: "width: 220px; flex-shrink: 0;", "Hash" }
+            }
+            for entry in entries {
+                div {
+                    key: "{entry.path}",
+                    DirectoryListingRow { collection_dataset: collection_dataset.clone(), entry }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DirectoryListingRow(collection_dataset: String, entry: common::file_browser::FileBrowserEntry) -> Element {
+    let nav_state = use_context::<FileBrowserNavState>();
+    let size_label = entry.size_bytes.map(format_size_bytes).unwrap_or_default();
+    let hash_label = entry.file_hash.clone().map(|h| h[..h.len().min(16)].to_string()).unwrap_or_default();
+
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                flex-direction: row;
+                align-items: center;
+                gap: 8px;
+                padding: 8px 16px;
+                cursor: pointer;
+                font-size: 14px;
+            ",
+            class: "hoover4-hover-shadow-background",
+            onclick: {
+                let entry = entry.clone();
+                move |_| {
+                    if entry.is_directory {
+                        let mut path_segments = nav_state.current_path_segments.read().clone();
+                        path_segments.push(entry.name.clone());
+                        nav_state.open_directory.call((collection_dataset.clone(), path_segments));
+                    } else if let Some(file_hash) = entry.file_hash.clone() {
+                        nav_state.select_file.call(file_hash);
+                    }
+                }
+            },
+            div {
+                style: "width: 20px; height: 20px; display: flex; align-items: center; justify-content: center; flex-shrink: 0;",
+                if entry.is_directory {
+                    Icon { icon: MdFolder, style: "width: 18px; height: 18px; color: rgba(0, 0, 0, 0.6);" }
+                } else {
+                    Icon { icon: MdInsertDriveFile, style: "width: 18px; height: 18px; color: rgba(0, 0, 0, 0.5);" }
+                }
+            }
+            span {
+                style: "flex: 1; min-width: 0; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
+                "{entry.name}"
+            }
+            span { style: "width: 100px; flex-shrink: 0; color: rgba(0, 0, 0, 0.6);", "{size_label}" }
+            span {
+                style: "width: 220px; flex-shrink: 0; color: rgba(0, 0, 0, 0.5); font-family: monospace; overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
+                "{hash_label}"
+            }
+        }
+    }
+}
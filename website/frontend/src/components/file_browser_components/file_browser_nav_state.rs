@@ -0,0 +1,20 @@
+//! Shared navigation state for the file browser page: which collection and
+//! directory path is open, and which file (if any) is selected for preview.
+
+use common::search_result::DocumentIdentifier;
+use dioxus::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileBrowserNavState {
+    pub current_collection: ReadSignal<Option<String>>,
+    pub current_path_segments: ReadSignal<Vec<String>>,
+    pub selected_result_hash: ReadSignal<Option<DocumentIdentifier>>,
+    pub open_directory: Callback<(String, Vec<String>)>,
+    pub select_file: Callback<String>,
+}
+
+impl FileBrowserNavState {
+    pub fn current_path_prefix(&self) -> String {
+        self.current_path_segments.read().join("/")
+    }
+}
@@ -0,0 +1,107 @@
+//! Bulk action bar shown above the result list when one or more result cards are selected.
+
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::{md_action_icons::MdClose, md_file_icons::MdFileDownload}};
+
+use crate::components::search_components::search_panel_left_view::SearchResultsState;
+
+#[component]
+pub fn BulkActionBar() -> Element {
+    let search_results_state = use_context::<SearchResultsState>();
+    let bulk_selection = search_results_state.bulk_selection;
+    let clear_bulk_selection = search_results_state.clear_bulk_selection;
+    let selected_count = bulk_selection.read().len();
+
+    let do_download_selected = use_callback(move |_: ()| {
+        let documents = bulk_selection.read().iter().cloned().collect::<Vec<_>>();
+        if documents.is_empty() {
+            return;
+        }
+        let toast_api = dioxus_primitives::toast::consume_toast();
+        toast_api
+            .info(
+                format!("Downloading {selected_count} documents."),
+                dioxus_primitives::toast::ToastOptions::new()
+                    .description("The selected documents are being downloaded to your computer.")
+                    .duration(std::time::Duration::from_secs(15))
+                    .permanent(false),
+            );
+        spawn(async move {
+            for document_identifier in documents {
+                let href = format!("/_download_document/{}/{}", document_identifier.collection_dataset, document_identifier.file_hash);
+                // One `eval` per document, each triggering its own anchor
+                // click, same mechanism as the single-document download link
+                // in `card_action_buttons.rs` — browsers only allow a
+                // handful of simultaneous same-gesture downloads, so firing
+                // these one at a time keeps every file from silently being
+                // dropped.
+                let mut trigger_download = document::eval(
+                    r#"
+                    const href = await dioxus.recv();
+                    const a = document.createElement("a");
+                    a.href = href;
+                    a.download = "";
+                    document.body.appendChild(a);
+                    a.click();
+                    a.remove();
+                    "#,
+                );
+                let _ = trigger_download.send(href);
+            }
+        });
+    });
+
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                flex-direction: row;
+                align-items: center;
+                gap: 12px;
+                background: #4096FF1A;
+                border: 1px solid #367ED899;
+                border-radius: 8px;
+                padding: 8px 16px;
+                margin: 8px 8px 0px 8px;
+            ",
+            span {
+                style: "font-size: 16px; font-weight: 400; color: rgb(0, 0, 0);",
+                "{selected_count} selected"
+            }
+            div { style: "flex: 1 1 auto;" }
+            button {
+                style: "
+                    display: flex;
+                    align-items: center;
+                    gap: 6px;
+                    cursor: pointer;
+                    border: 1px solid #000;
+                    border-radius: 8px;
+                    background: white;
+                    padding: 6px 10px;
+                    font-size: 15px;
+                ",
+                class: "hoover4-hover-shadow-background",
+                onclick: move |_| do_download_selected.call(()),
+                Icon { icon: MdFileDownload, style: "width: 18px; height: 18px;" }
+                "Download"
+            }
+            button {
+                style: "
+                    display: flex;
+                    align-items: center;
+                    gap: 6px;
+                    cursor: pointer;
+                    border: none;
+                    background: transparent;
+                    padding: 6px 10px;
+                    font-size: 15px;
+                    color: rgba(0, 0, 0, 0.6);
+                ",
+                onclick: move |_| clear_bulk_selection.call(()),
+                Icon { icon: MdClose, style: "width: 18px; height: 18px;" }
+                "Clear selection"
+            }
+        }
+    }
+}
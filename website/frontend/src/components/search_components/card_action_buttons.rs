@@ -4,10 +4,14 @@ use common::search_result::DocumentIdentifier;
 use dioxus::prelude::*;
 use dioxus_free_icons::{Icon, icons::{md_action_icons::MdOpenInNew, md_editor_icons::MdInsertLink, md_file_icons::MdFileDownload, md_navigation_icons::MdMoreVert}};
 
-use crate::routes::Route;
+use crate::{data_definitions::doc_viewer_state::DocViewerState, routes::Route};
 
 #[component]
-pub fn DocCardActionButtonOpenNewTab(document_identifier:ReadSignal<DocumentIdentifier>) -> Element {
+pub fn DocCardActionButtonOpenNewTab(document_identifier:ReadSignal<DocumentIdentifier>, #[props(default)] doc_viewer_state: Option<DocViewerState>) -> Element {
+    let href = match doc_viewer_state {
+        Some(state) => Route::view_document_page_at_match(document_identifier.read().clone(), state),
+        None => Route::view_document_page(document_identifier.read().clone()),
+    }.to_string();
     rsx! {
         a {
             style: "
@@ -27,7 +31,7 @@ pub fn DocCardActionButtonOpenNewTab(document_identifier:ReadSignal<DocumentIden
             ",
             target: "_blank",
             class: "hoover4-hover-shadow-background",
-            href: Route::ViewDocumentPage { document_identifier: document_identifier.read().clone().into() }.to_string(),
+            href,
             // onclick: move |_e| {
             //     _e.prevent_default();
             //     _e.stop_propagation();
@@ -2,7 +2,7 @@ use std::collections::BTreeSet;
 
 use dioxus::prelude::*;
 use common::{search_query::SearchQuery, search_result::FacetOriginalValue};
-use dioxus_free_icons::{Icon, icons::{md_action_icons::MdInfo, md_communication_icons::MdBusiness, md_social_icons::MdPerson, md_toggle_icons::{MdCheckBox, MdCheckBoxOutlineBlank}}};
+use dioxus_free_icons::{Icon, icons::{md_action_icons::{MdInfo, MdSearch}, md_communication_icons::MdBusiness, md_social_icons::MdPerson, md_toggle_icons::{MdCheckBox, MdCheckBoxOutlineBlank}}};
 
 use dioxus_free_icons::icons::{go_icons::GoDatabase, md_communication_icons::MdLocationOn, md_editor_icons::MdInsertDriveFile, md_navigation_icons::{MdApps, MdArrowDropDown}};
 use crate::{api::search_api::search_string_facet, components::{error_boundary::ComponentErrorDisplay, suspend_boundary::SuspendWrapper}};
@@ -254,9 +254,20 @@ fn FacetButton<I: dioxus_free_icons::IconShape+'static+Clone+PartialEq>(
 
 #[component]
 fn FacetSelectorList(original_query: ReadSignal<SearchQuery>, modified_search_query: Signal<SearchQuery>, facet_field_name: ReadSignal<String>, map_string_terms: ReadSignal<Option<String>>) -> Element {
+    let mut facet_search_text = use_signal(|| "".to_string());
+    let mut shown_limit = use_signal(|| common::search_const::FACET_VALUES_PAGE_SIZE);
+    // reset paging/search when the facet popover is re-opened on a different facet
+    use_effect(move || {
+        let _ = facet_field_name.read();
+        facet_search_text.set("".to_string());
+        shown_limit.set(common::search_const::FACET_VALUES_PAGE_SIZE);
+    });
+
     let search_result = use_resource(move || {
         let q = original_query.read().clone();
-        search_string_facet(q, facet_field_name.read().clone(), map_string_terms.read().clone())
+        let search_text = facet_search_text.read().clone();
+        let limit = *shown_limit.read();
+        search_string_facet(q, facet_field_name.read().clone(), map_string_terms.read().clone(), Some(search_text).filter(|s| !s.is_empty()), limit)
     }).suspend()?.cloned();
     let mut search_result = match search_result {
         Err(e) => return rsx! {ComponentErrorDisplay { error_txt: format!("{:#?}", e) }},
@@ -273,6 +284,28 @@ fn FacetSelectorList(original_query: ReadSignal<SearchQuery>, modified_search_qu
         });
     }
     rsx! {
+        div {
+            style: "
+                display: flex;
+                align-items: center;
+                gap: 6px;
+                padding: 4px 8px;
+                margin-bottom: 6px;
+                border: 1px solid rgba(0,0,0,0.3);
+                border-radius: 8px;
+            ",
+            Icon { icon: MdSearch, style: "width: 18px; height: 18px; color: rgba(0,0,0,0.6); flex-shrink: 0;" }
+            input {
+                r#type: "text",
+                placeholder: "Search values",
+                style: "flex: 1; border: none; outline: none; font-size: 16px; min-width: 0;",
+                value: "{facet_search_text}",
+                oninput: move |event| {
+                    facet_search_text.set(event.value());
+                    shown_limit.set(common::search_const::FACET_VALUES_PAGE_SIZE);
+                },
+            }
+        }
         ul {
             for result in search_result.facet_values {
                 li {
@@ -286,7 +319,24 @@ fn FacetSelectorList(original_query: ReadSignal<SearchQuery>, modified_search_qu
                     }
                 }
             }
-
+        }
+        if search_result.has_more {
+            button {
+                style: "
+                    width: 100%;
+                    padding: 6px;
+                    margin-top: 4px;
+                    cursor: pointer;
+                    border: 1px solid rgba(0,0,0,0.3);
+                    border-radius: 8px;
+                    background: white;
+                    font-size: 15px;
+                ",
+                onclick: move |_| {
+                    *shown_limit.write() += common::search_const::FACET_VALUES_PAGE_SIZE;
+                },
+                "Show more"
+            }
         }
     }
 }
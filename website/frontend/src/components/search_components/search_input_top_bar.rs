@@ -1,12 +1,14 @@
 use dioxus::prelude::*;
-use common::search_query::SearchQuery;
-use dioxus_free_icons::{Icon, icons::{go_icons::GoDatabase, md_action_icons::MdSearch, md_communication_icons::MdLocationOn, md_editor_icons::MdInsertDriveFile, md_navigation_icons::{MdApps, MdArrowDropDown}}};
-use crate::{components::{search_components::search_facets::{FacetButtonStrip}, suspend_boundary::SuspendWrapper}, routes::Route};
+use common::search_query::{SearchQuery, SearchQueryMode};
+use dioxus_free_icons::{Icon, icons::{go_icons::GoDatabase, md_action_icons::MdSearch, md_communication_icons::MdLocationOn, md_editor_icons::{MdInsertDriveFile, MdInsertLink}, md_navigation_icons::{MdApps, MdArrowDropDown}}};
+use crate::{api::{saved_searches_api::save_search, search_api::{search_for_results_hit_count, search_suggestions}}, components::{search_components::{search_facets::{FacetButtonStrip}, search_suggestions_dropdown::{SearchSuggestionsDropdown, complete_last_token}, unified_search_preview::UnifiedSearchPreview}, suspend_boundary::SuspendWrapper}, data_definitions::url_param::UrlParam, routes::Route};
 
 
 #[component]
 pub fn SearchInputTopBar(original_query: ReadSignal<SearchQuery>) -> Element {
     let mut modified_search_query = use_signal(|| original_query.read().clone());
+    let mut preview_expanded = use_signal(|| false);
+    let mut highlighted_suggestion = use_signal(|| 0usize);
     // when url changes (the read signal given to us), we need to update the signals, as they are not reset by navigation.
     use_effect(move || {
         let new_query = original_query.read().clone();
@@ -22,58 +24,314 @@ pub fn SearchInputTopBar(original_query: ReadSignal<SearchQuery>) -> Element {
         let new_q = event.value();
         modified_search_query.write().query_string = new_q;
     };
+
+    // ~150ms debounce: `document::eval` sleeps before the fetch, and
+    // `use_resource` drops the previous future as soon as the query string
+    // changes again, so only the last keystroke's suggestions actually land.
+    let suggestions_res = use_resource(move || {
+        let prefix = modified_search_query.read().query_string.clone();
+        async move {
+            let mut timer = document::eval("await new Promise(r => setTimeout(r, 150)); dioxus.send(true);");
+            let _ = timer.recv::<bool>().await;
+            search_suggestions(prefix, 8).await
+        }
+    });
+    let suggestions = suggestions_res.read().as_ref().and_then(|r| r.as_ref().ok()).cloned().unwrap_or_default();
+    use_effect(move || {
+        let _ = modified_search_query.read().query_string.clone();
+        highlighted_suggestion.set(0);
+    });
+    let select_suggestion = move |index: usize| {
+        if let Some(s) = suggestions.get(index) {
+            modified_search_query.write().query_string = complete_last_token(&modified_search_query.read().query_string, &s.term);
+        }
+    };
+
     let search_onkeydown = move |event: Event<KeyboardData>| {
-        if event.key() == Key::Enter {
-            trigger_search(());
+        match event.key() {
+            Key::ArrowDown if preview_expanded() && !suggestions.is_empty() => {
+                event.prevent_default();
+                highlighted_suggestion.set((highlighted_suggestion() + 1) % suggestions.len());
+            }
+            Key::ArrowUp if preview_expanded() && !suggestions.is_empty() => {
+                event.prevent_default();
+                highlighted_suggestion.set((highlighted_suggestion() + suggestions.len() - 1) % suggestions.len());
+            }
+            Key::Enter => {
+                if preview_expanded() && !suggestions.is_empty() {
+                    select_suggestion(highlighted_suggestion());
+                } else {
+                    preview_expanded.set(false);
+                    trigger_search(());
+                }
+            }
+            _ => {}
         }
     };
+    let do_copy_search_link = use_callback(move |_: ()| {
+        let url = web_sys::window().unwrap().location().href().unwrap();
+        let _r = web_sys::window().unwrap().navigator().clipboard().write_text(&url);
+        dioxus::logger::tracing::info!("Search link copied to clipboard: {:#?}", url);
+
+        let toast_api = dioxus_primitives::toast::consume_toast();
+        toast_api
+            .info(
+                "Search link copied to clipboard.".to_string(),
+                dioxus_primitives::toast::ToastOptions::new()
+                    .description("This search, including the current filters, has been copied to your clipboard.")
+                    .duration(std::time::Duration::from_secs(15))
+                    .permanent(false),
+            );
+    });
+    let mut save_search_expanded = use_signal(|| false);
+    let mut save_search_name = use_signal(String::new);
+    let mut save_search_status = use_signal(|| None::<String>);
+    let do_save_search = move |_: ()| {
+        let name = save_search_name.read().trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let query = modified_search_query.read().clone();
+        let encoded_query = UrlParam::from(query.clone()).to_string();
+        save_search_expanded.set(false);
+        save_search_status.set(Some("Saving…".to_string()));
+        spawn(async move {
+            let hit_count = search_for_results_hit_count(query).await.ok();
+            match save_search(name, encoded_query, hit_count).await {
+                Ok(_) => {
+                    save_search_name.set(String::new());
+                    save_search_status.set(Some("Search saved.".to_string()));
+                }
+                Err(e) => {
+                    dioxus::logger::tracing::error!("save_search failed: {e}");
+                    save_search_status.set(Some(format!("! save failed: {e}")));
+                }
+            }
+        });
+    };
     rsx! {
         div {
-            id: "x-search-input-search-box",
+            style: "position: relative; margin-left: 16px;",
+            div {
+                id: "x-search-input-search-box",
+                style: "
+                    display:flex;
+                    align-items:center;
+                    gap: 16px;
+                    padding: 16px;
+                    border-bottom: 1px;
+                    background-color: white;
+                    border-radius: 9999px;
+                    padding: 10px 14px;
+                    height: 44px;
+                    color: #111827;
+                    border: 1px solid rgba(101, 101, 101, 0.8);
+                    width: 500px;
+
+                ",
+
+                button {
+                    style: "
+                        border: none;
+                        background: none;
+                        cursor: pointer;
+                    ",
+                    onclick: move |_| {
+                        preview_expanded.set(false);
+                        trigger_search(())
+                    },
+                    Icon { icon: MdSearch, style: "width: 20px; height: 20px; color:{search_button_color()};" }
+                }
+                input {
+                    r#type: "text",
+                    placeholder: "Search in knowledgebase",
+                    style: "
+                        flex:1;
+                        border: none;
+                        outline: none;
+                        background: transparent;
+                        color: #111827;
+                        font-size: 20px;
+                        font-weight: 400;
+                        font-family: Roboto, sans-serif;
+                    ",
+                    value: "{modified_search_query.read().query_string}",
+                    oninput: search_oninput,
+                    onkeydown: search_onkeydown,
+                    onfocus: move |_| { preview_expanded.set(true); },
+                }
+            }
+            if preview_expanded() {
+                div {
+                    style: "
+                        position: fixed;
+                        top: 0px;
+                        left: 0px;
+                        width: 100vw;
+                        height: 100vh;
+                        z-index: 1000;
+                    ",
+                    onclick: move |_| { preview_expanded.set(false); },
+                }
+                div {
+                    style: "
+                        position: absolute;
+                        top: calc(100% + 6px);
+                        left: 0px;
+                        width: 520px;
+                        background-color: white;
+                        border: 1px solid rgba(0, 0, 0, 0.5);
+                        box-shadow: 0 0 10px 0 rgba(0, 0, 0, 0.5);
+                        border-radius: 12px;
+                        overflow: hidden;
+                        z-index: 1001;
+                    ",
+                    onclick: move |_e| { _e.stop_propagation(); },
+                    if !suggestions.is_empty() {
+                        div {
+                            style: "border-bottom: 1px solid rgba(0, 0, 0, 0.1);",
+                            SearchSuggestionsDropdown { suggestions: suggestions.clone(), highlighted: highlighted_suggestion(), on_select: select_suggestion }
+                        }
+                    }
+                    UnifiedSearchPreview { search_query: modified_search_query }
+                }
+            }
+        }
+        button {
+            title: "Search mode: {modified_search_query.read().query_mode.label()}. Click to cycle Keyword / Exact phrase / Regex.",
             style: "
-                display:flex;
-                align-items:center;
-                gap: 16px;
-                padding: 16px;
-                border-bottom: 1px;
-                background-color: white;
-                border-radius: 9999px;
-                padding: 10px 14px;
                 height: 44px;
+                padding: 0 14px;
+                cursor: pointer;
+                border: 1px solid rgba(101, 101, 101, 0.8);
+                border-radius: 9999px;
+                background: white;
                 color: #111827;
+                font-size: 14px;
+            ",
+            class: "hoover4-hover-shadow-background",
+            onclick: move |_| {
+                let next = modified_search_query.read().query_mode.next();
+                modified_search_query.write().query_mode = next;
+            },
+            "{modified_search_query.read().query_mode.label()}"
+        }
+        button {
+            title: "Copy link to this search",
+            style: "
+                width: 44px;
+                height: 44px;
+                cursor: pointer;
                 border: 1px solid rgba(101, 101, 101, 0.8);
-                width: 500px;
-                margin-left: 16px;
-
+                border-radius: 9999px;
+                background: white;
+                color: #111827;
+                display: flex;
+                align-items: center;
+                justify-content: center;
+                padding: 1px;
             ",
-
+            class: "hoover4-hover-shadow-background",
+            onclick: move |_| {
+                do_copy_search_link.call(());
+            },
+            Icon { icon: MdInsertLink, style: "width: 20px; height: 20px;" }
+        }
+        div {
+            style: "position: relative;",
             button {
+                title: "Save this search for quick recall later",
                 style: "
-                    border: none;
-                    background: none;
+                    height: 44px;
+                    padding: 0 14px;
                     cursor: pointer;
+                    border: 1px solid rgba(101, 101, 101, 0.8);
+                    border-radius: 9999px;
+                    background: white;
+                    color: #111827;
+                    font-size: 14px;
                 ",
-                onclick: move |_| {
-                    trigger_search(())
+                class: "hoover4-hover-shadow-background",
+                onclick: move |_e| {
+                    _e.stop_propagation();
+                    save_search_status.set(None);
+                    *save_search_expanded.write() ^= true;
                 },
-                Icon { icon: MdSearch, style: "width: 20px; height: 20px; color:{search_button_color()};" }
+                "Save search"
             }
-            input {
-                r#type: "text",
-                placeholder: "Search in knowledgebase",
-                style: "
-                    flex:1;
-                    border: none;
-                    outline: none;
-                    background: transparent;
-                    color: #111827;
-                    font-size: 20px;
-                    font-weight: 400;
-                    font-family: Roboto, sans-serif;
-                ",
-                value: "{modified_search_query.read().query_string}",
-                oninput: search_oninput,
-                onkeydown: search_onkeydown,
+            if let Some(status) = save_search_status.read().clone() {
+                div {
+                    style: "
+                        position: absolute;
+                        top: calc(100% + 6px);
+                        left: 0px;
+                        font-size: 13px;
+                        color: rgba(0, 0, 0, 0.6);
+                        white-space: nowrap;
+                    ",
+                    "{status}"
+                }
+            }
+            if save_search_expanded() {
+                div {
+                    style: "
+                        position: fixed;
+                        top: 0px;
+                        left: 0px;
+                        width: 100vw;
+                        height: 100vh;
+                        z-index: 1000;
+                    ",
+                    onclick: move |_| { save_search_expanded.set(false); },
+                }
+                div {
+                    style: "
+                        position: absolute;
+                        top: calc(100% + 6px);
+                        left: 0px;
+                        width: 260px;
+                        background-color: white;
+                        border: 1px solid rgba(0, 0, 0, 0.5);
+                        box-shadow: 0 0 10px 0 rgba(0, 0, 0, 0.5);
+                        border-radius: 12px;
+                        padding: 10px;
+                        gap: 8px;
+                        z-index: 1001;
+                        display: flex;
+                        flex-direction: column;
+                    ",
+                    onclick: move |_e| _e.stop_propagation(),
+                    input {
+                        r#type: "text",
+                        placeholder: "Name this search",
+                        style: "
+                            border: 1px solid rgba(0, 0, 0, 0.2);
+                            border-radius: 6px;
+                            padding: 6px 8px;
+                            font-size: 14px;
+                        ",
+                        value: "{save_search_name}",
+                        oninput: move |e| save_search_name.set(e.value()),
+                        onkeydown: move |e: Event<KeyboardData>| {
+                            if e.key() == Key::Enter {
+                                do_save_search(());
+                            }
+                        },
+                    }
+                    button {
+                        style: "
+                            height: 32px;
+                            border: none;
+                            border-radius: 6px;
+                            background: #4F46E5;
+                            color: white;
+                            cursor: pointer;
+                            font-size: 14px;
+                        ",
+                        onclick: move |_| do_save_search(()),
+                        "Save"
+                    }
+                }
             }
         }
         FacetButtonStrip{original_query, modified_search_query, trigger_search}
@@ -1,21 +1,38 @@
 //! Left panel view for search filters and facets.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use dioxus::prelude::*;
+use futures::StreamExt;
 
-use common::{search_query::SearchQuery, search_result::{DocumentIdentifier, SearchResultDocuments}};
-use crate::{api::search_api::{search_for_results, search_for_results_hit_count}, components::{error_boundary::ComponentErrorDisplay, search_components::{search_result_item_card::SearchResultItemCard, search_result_list_controls::SearchResultListControls}, suspend_boundary::{LoadingIndicator, SuspendWrapper}}, data_definitions::doc_viewer_state::DocViewerState, routes::Route};
+use common::{search_query::SearchQuery, search_result::{DocumentIdentifier, SearchResultDocuments, SearchResultSnippet}};
+use crate::{api::search_api::{search_for_results, search_for_results_hit_count, stream_search_snippets}, components::{error_boundary::ComponentErrorDisplay, search_components::{bulk_action_bar::BulkActionBar, search_result_item_card::SearchResultItemCard, search_result_list_controls::SearchResultListControls}, suspend_boundary::{LoadingIndicator, SuspendWrapper}}, data_definitions::doc_viewer_state::DocViewerState, routes::Route};
 #[derive(Copy, Clone)]
 pub struct SearchResultsState {
     // pub query: ReadSignal<SearchQuery>,
+    /// The active query's text, for cards that need to deep-link back into
+    /// the document viewer at a specific match (see `DocViewerState`).
+    pub query_string: ReadSignal<String>,
     pub hit_count: ReadSignal<Option<Result<u64, ServerFnError>>>,
     pub search_result: ReadSignal<Option<Result<SearchResultDocuments, ServerFnError>>>,
+    /// Snippets filled in progressively by the `stream_search_snippets`
+    /// follow-up, keyed by document so cards can patch themselves in as
+    /// each one arrives without waiting for the whole page.
+    pub loaded_snippets: ReadSignal<BTreeMap<DocumentIdentifier, SearchResultSnippet>>,
     pub current_search_result_page: ReadSignal<u64>,
     pub set_current_page: Callback<u64>,
     pub selected_result_hash: ReadSignal<Option<DocumentIdentifier>>,
     pub set_selected_result_hash: Callback<Option<DocumentIdentifier>>,
     pub set_selected_result_hash_and_page: Callback<(Option<DocumentIdentifier>, u64)>,
+    /// Moves the selection to the previous/next result, crossing a page
+    /// boundary via `prev_hash`/`next_hash` when the list edge is hit.
+    /// Shared between `ControlNextPrevDocument`'s buttons and the `j`/`k`
+    /// keyboard shortcuts so both go through the exact same logic.
+    pub go_previous_result: Callback<()>,
+    pub go_next_result: Callback<()>,
+    pub bulk_selection: ReadSignal<BTreeSet<DocumentIdentifier>>,
+    pub toggle_bulk_selection: Callback<DocumentIdentifier>,
+    pub clear_bulk_selection: Callback<()>,
 }
 
 #[component]
@@ -44,6 +61,38 @@ pub fn SearchPanelLeftView(query: ReadSignal<SearchQuery>, current_search_result
         search_result.restart();
     });
 
+    let mut loaded_snippets = use_signal(|| BTreeMap::<DocumentIdentifier, SearchResultSnippet>::new());
+    // re-stream snippets whenever the page or query changes, dropping any snippets collected for the old page
+    use_effect(move || {
+        let q = query.read().clone();
+        let page = *current_search_result_page.read();
+        loaded_snippets.write().clear();
+        spawn(async move {
+            let mut stream = match stream_search_snippets(q, page).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    dioxus::logger::tracing::error!("stream_search_snippets failed: {e}");
+                    return;
+                }
+            };
+            while let Some(line) = stream.next().await {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(e) => {
+                        dioxus::logger::tracing::error!("stream_search_snippets stream error: {e}");
+                        continue;
+                    }
+                };
+                match serde_json::from_str::<SearchResultSnippet>(&line) {
+                    Ok(snippet) => {
+                        loaded_snippets.write().insert(snippet.document_identifier(), snippet);
+                    }
+                    Err(e) => dioxus::logger::tracing::error!("stream_search_snippets decode error: {e}"),
+                }
+            }
+        });
+    });
+
 
     let set_current_page = Callback::new(move |page: u64| {
         let route = Route::SearchPage {
@@ -72,16 +121,120 @@ pub fn SearchPanelLeftView(query: ReadSignal<SearchQuery>, current_search_result
         };
         navigator().push(route);
     });
+
+    // Shared by `ControlNextPrevDocument`'s buttons and the global keyboard
+    // shortcuts below, so both paths advance/retreat through the result
+    // list (and across the page boundary via `prev_hash`/`next_hash`) the
+    // same way.
+    let go_previous_result = Callback::new(move |_: ()| {
+        let result_hashes = search_result.read().as_ref().ok().map(|r| r.results.iter().map(|r| r.document_identifier()).collect::<Vec<_>>()).unwrap_or_default();
+        let current_list_position = selected_result_hash.read().as_ref().and_then(|hash| result_hashes.iter().position(|h| h == hash));
+        let Some(current_list_position) = current_list_position else { return };
+        if current_list_position == 0 {
+            let Some(Ok(search_result)) = search_result.read().as_ref().cloned() else { return };
+            let Some(prev_hash) = search_result.prev_hash else { return };
+            if *current_search_result_page.read() > 0 {
+                set_selected_result_hash_and_page.call((Some(prev_hash), *current_search_result_page.read() - 1));
+            }
+        } else {
+            set_selected_result_hash.call(Some(result_hashes[current_list_position - 1].clone()));
+        }
+    });
+    let go_next_result = Callback::new(move |_: ()| {
+        let result_hashes = search_result.read().as_ref().ok().map(|r| r.results.iter().map(|r| r.document_identifier()).collect::<Vec<_>>()).unwrap_or_default();
+        let current_list_position = selected_result_hash.read().as_ref().and_then(|hash| result_hashes.iter().position(|h| h == hash));
+        let Some(current_list_position) = current_list_position else { return };
+        if current_list_position == result_hashes.len() - 1 {
+            let Some(Ok(search_result)) = search_result.read().as_ref().cloned() else { return };
+            let Some(next_hash) = search_result.next_hash else { return };
+            set_selected_result_hash_and_page.call((Some(next_hash), *current_search_result_page.read() + 1));
+        } else {
+            set_selected_result_hash.call(Some(result_hashes[current_list_position + 1].clone()));
+        }
+    });
+
+    let mut bulk_selection = use_signal(|| BTreeSet::<DocumentIdentifier>::new());
+    let toggle_bulk_selection = Callback::new(move |document_identifier: DocumentIdentifier| {
+        let mut bulk_selection = bulk_selection.write();
+        if bulk_selection.contains(&document_identifier) {
+            bulk_selection.remove(&document_identifier);
+        } else {
+            bulk_selection.insert(document_identifier);
+        }
+    });
+    let clear_bulk_selection = Callback::new(move |_: ()| {
+        bulk_selection.write().clear();
+    });
+    // selection is tied to a single query's results, so a new search should start clean
+    use_effect(move || {
+        let _ = query.read();
+        bulk_selection.write().clear();
+    });
+
+    let query_string = use_memo(move || query.read().query_string.clone());
     use_context_provider(move || SearchResultsState {
+        query_string: query_string.into(),
         hit_count: hit_count.into(),
         search_result: search_result.into(),
+        loaded_snippets: loaded_snippets.into(),
         current_search_result_page,
         set_current_page,
         selected_result_hash,
         set_selected_result_hash,
         set_selected_result_hash_and_page,
+        go_previous_result,
+        go_next_result,
+        bulk_selection: bulk_selection.into(),
+        toggle_bulk_selection,
+        clear_bulk_selection,
     });
 
+    // Global `j`/`k`/arrow-key result navigation and `h`/`l`/PageUp/PageDown
+    // page navigation, for triaging large result sets without reaching for
+    // the mouse. The listener lives for as long as `SearchResultsState` is
+    // in context (this component), and is suppressed in JS while an
+    // `input`/`textarea` (or any `contenteditable`) has focus.
+    use_effect(move || {
+        spawn(async move {
+            let mut eval = document::eval(r#"
+                function hoover4IsEditableFocus() {
+                    const el = document.activeElement;
+                    if (!el) return false;
+                    const tag = el.tagName ? el.tagName.toLowerCase() : "";
+                    return tag === "input" || tag === "textarea" || el.isContentEditable;
+                }
+                function hoover4KeydownHandler(e) {
+                    if (hoover4IsEditableFocus()) return;
+                    dioxus.send(e.key);
+                }
+                window.addEventListener("keydown", hoover4KeydownHandler);
+            "#);
+            loop {
+                match eval.recv::<String>().await {
+                    Ok(key) => match key.as_str() {
+                        "j" | "ArrowDown" => go_next_result.call(()),
+                        "k" | "ArrowUp" => go_previous_result.call(()),
+                        "h" | "PageUp" => {
+                            let page = *current_search_result_page.read();
+                            if page > 0 {
+                                set_current_page.call(page - 1);
+                            }
+                        }
+                        "l" | "PageDown" => {
+                            let hit_count = hit_count.read().clone().and_then(|c| c.ok()).unwrap_or(0);
+                            let max_page = hit_count.saturating_sub(1) / common::search_const::PAGE_SIZE;
+                            let page = *current_search_result_page.read();
+                            if page < max_page {
+                                set_current_page.call(page + 1);
+                            }
+                        }
+                        _ => {}
+                    },
+                    Err(_) => break,
+                }
+            }
+        });
+    });
 
     rsx! {
         div {
@@ -97,6 +250,9 @@ pub fn SearchPanelLeftView(query: ReadSignal<SearchQuery>, current_search_result
                 width: 100%;
             ",
             SearchResultListControls {}
+            if !bulk_selection.read().is_empty() {
+                BulkActionBar {}
+            }
 
             div {
                 style: "
@@ -140,6 +296,32 @@ fn SearchResultsView() -> Element {
     });
 
     rsx! {
+        if search_result.timed_out {
+            div {
+                style: "
+                    padding: 8px 12px;
+                    margin-bottom: 4px;
+                    background-color: #fff3cd;
+                    color: #664d03;
+                    border-radius: 4px;
+                    font-size: 13px;
+                ",
+                "Results may be incomplete — search timed out."
+            }
+        }
+        if search_result.cache_hit {
+            div {
+                style: "
+                    padding: 8px 12px;
+                    margin-bottom: 4px;
+                    background-color: #e7f1ff;
+                    color: #084298;
+                    border-radius: 4px;
+                    font-size: 13px;
+                ",
+                "Served from cache (originally searched in {search_result.cache_duration_ms}ms)."
+            }
+        }
         ul {
             id: "x-search-panel-results-wrapper",
             style: "
@@ -2,9 +2,9 @@
 
 use dioxus::{logger::tracing, prelude::*};
 use common::{search_result::{DocumentIdentifier, SearchResultDocumentItem}, text_highlight::HighlightTextSpan};
-use dioxus_free_icons::{Icon, icons::{go_icons::GoDatabase, md_action_icons::{MdDonutLarge, MdOpenInNew}, md_editor_icons::MdInsertDriveFile, md_navigation_icons::MdMoreVert}};
+use dioxus_free_icons::{Icon, icons::{go_icons::GoDatabase, md_action_icons::{MdDonutLarge, MdOpenInNew}, md_editor_icons::MdInsertDriveFile, md_navigation_icons::MdMoreVert, md_toggle_icons::{MdCheckBox, MdCheckBoxOutlineBlank}}};
 
-use crate::{components::search_components::{card_action_buttons::{DocCardActionButtonMore, DocCardActionButtonOpenNewTab}, search_panel_left_view::SearchResultsState}, routes::Route};
+use crate::{components::search_components::{card_action_buttons::{DocCardActionButtonMore, DocCardActionButtonOpenNewTab}, search_panel_left_view::SearchResultsState}, data_definitions::doc_viewer_state::DocViewerState, pages::search_page::ResultCardDisplaySettingsControl, routes::Route};
 
 #[component]
 pub fn SearchResultItemCard(result: ReadSignal<SearchResultDocumentItem>, onmounted: Callback<Event<MountedData>>) -> Element {
@@ -12,22 +12,46 @@ pub fn SearchResultItemCard(result: ReadSignal<SearchResultDocumentItem>, onmoun
     let current_search_result_page = search_results_state.current_search_result_page;
     let set_selected_result_hash = search_results_state.set_selected_result_hash;
     let selected_result_hash = search_results_state.selected_result_hash;
+    let bulk_selection = search_results_state.bulk_selection;
+    let toggle_bulk_selection = search_results_state.toggle_bulk_selection;
+    let is_bulk_selected = bulk_selection.read().contains(&result.read().document_identifier());
+    let display_settings = use_context::<ResultCardDisplaySettingsControl>().settings;
+    let density = display_settings.read().density;
+    let fields = display_settings.read().fields;
     let SearchResultDocumentItem {
         title,
-        highlight_text_spans,
-        highlight_filenames_spans,
+        mut highlight_text_spans,
+        mut highlight_filenames_spans,
         file_hash,
         collection_dataset,
         result_index_in_page,
+        mut snippets_loaded,
     } = result.read().clone();
+    let loaded_snippets = search_results_state.loaded_snippets;
+    let mut deep_link_state = None;
+    if let Some(snippet) = loaded_snippets.read().get(&result.read().document_identifier()) {
+        highlight_text_spans = snippet.highlight_text_spans.clone();
+        highlight_filenames_spans = snippet.highlight_filenames_spans.clone();
+        snippets_loaded = true;
+        // Jump straight to the snippet's top match when opening the document,
+        // instead of landing with no indication of where the hit was.
+        let top_match_index = snippet.highlight_text_spans.iter().find(|span| span.is_highlighted).map(|span| span.index);
+        deep_link_state = Some(DocViewerState::from_search_match(search_results_state.query_string.read().clone(), snippet.page_id, top_match_index));
+    }
     let we_are_selected = selected_result_hash.read().clone() == Some(result().document_identifier());
 
     let item_index = 1 + (*current_search_result_page.read() * common::search_const::PAGE_SIZE) + result_index_in_page;
     let border_color = if we_are_selected { "#367ED899" } else { "#AAAAAA33" };
     let background_color = if we_are_selected { "#4096FF33" } else { "white" };
+    // Gives the keyboard-focused row (navigated via `j`/`k`/arrow keys) a
+    // visible ring even before the pointer hovers it, since the existing
+    // border/background tint alone is easy to miss while scanning.
+    let focus_outline = if we_are_selected { "2px solid #367ED8" } else { "2px solid transparent" };
+    let card_height = density.card_height_px();
 
     rsx! {
         div {
+            class: "hoover4-hover-shadow-background",
             style: "
                 display: flex;
                 flex-direction: column;
@@ -35,10 +59,12 @@ pub fn SearchResultItemCard(result: ReadSignal<SearchResultDocumentItem>, onmoun
                 gap: 7px;
                 background: {background_color};
                 border: 3px solid {border_color};
+                outline: {focus_outline};
+                outline-offset: 1px;
                 border-radius: 8px;
                 padding: 12px 16px;
                 margin: 8px 8px;
-                height: 148px;
+                height: {card_height}px;
                 width: calc(100% - 16px);
                 box-sizing: border-box;
             ",
@@ -59,14 +85,36 @@ pub fn SearchResultItemCard(result: ReadSignal<SearchResultDocumentItem>, onmoun
                     padding: 1px;
                     border: 1px;
                 ",
-                span {
-                    style: "font-size: 20px; font-weight: 200; color: rgba(0, 0, 0, 0.5); padding: 1px 4px; border-radius: 4px; margin: -4px",
-                    "{item_index}."
+                if fields.show_item_index {
+                    span {
+                        style: "font-size: 20px; font-weight: 200; color: rgba(0, 0, 0, 0.5); padding: 1px 4px; border-radius: 4px; margin: -4px",
+                        "{item_index}."
+                    }
+                }
+                div {
+                    style: "
+                        display: flex;
+                        align-items: center;
+                        justify-content: center;
+                        flex-shrink: 0;
+                        cursor: pointer;
+                    ",
+                    onclick: move |_e| {
+                        _e.stop_propagation();
+                        toggle_bulk_selection(result().document_identifier());
+                    },
+                    if is_bulk_selected {
+                        Icon { icon: MdCheckBox, style: "width: 22px; height: 22px; color: rgb(28, 33, 45);" }
+                    } else {
+                        Icon { icon: MdCheckBoxOutlineBlank, style: "width: 22px; height: 22px; color: rgba(0, 0, 0, 0.4);" }
+                    }
                 }
                 // ICON FOR TITLE
-                FileTypeIcon {}
+                if fields.show_file_type_icon {
+                    FileTypeIcon {}
+                }
                 // TITLE
-                CardTitleSection {highlight_filenames_spans}
+                CardTitleSection {title, highlight_filenames_spans, snippets_loaded}
 
                 // SPACER
                 div {
@@ -74,11 +122,13 @@ pub fn SearchResultItemCard(result: ReadSignal<SearchResultDocumentItem>, onmoun
                         flex: 1 1 auto;
                     ",
                 }
-                // ICON FOR COLLECTION
-                CollectionIcon {}
+                if fields.show_collection_name {
+                    // ICON FOR COLLECTION
+                    CollectionIcon {}
 
-                // COLLECTION NAME
-                ComponentNameSection {collection_dataset}
+                    // COLLECTION NAME
+                    ComponentNameSection {collection_dataset}
+                }
             }
             // Row 2: TEXT SNIPPET - BUTTONS
             div {
@@ -94,7 +144,9 @@ pub fn SearchResultItemCard(result: ReadSignal<SearchResultDocumentItem>, onmoun
                     padding: 2px;
                     border: 2px;
                 ",
-                HighlightTextSnippetSection {highlight_text_spans}
+                if fields.show_snippet {
+                    HighlightTextSnippetSection {highlight_text_spans, snippets_loaded, line_clamp: density.snippet_line_clamp()}
+                }
                 div {
                     style: "
                         display: flex;
@@ -103,7 +155,7 @@ pub fn SearchResultItemCard(result: ReadSignal<SearchResultDocumentItem>, onmoun
                         gap: 8px;
                         flex-shrink: 0;
                     ",
-                    DocCardActionButtonOpenNewTab {document_identifier: result().document_identifier()}
+                    DocCardActionButtonOpenNewTab {document_identifier: result().document_identifier(), doc_viewer_state: deep_link_state.clone()}
                     DocCardActionButtonMore {document_identifier: result().document_identifier()}
                 }
             }
@@ -138,7 +190,7 @@ fn FileTypeIcon() -> Element {
 }
 
 #[component]
-fn CardTitleSection(highlight_filenames_spans: Vec<HighlightTextSpan>) -> Element {
+fn CardTitleSection(title: String, highlight_filenames_spans: Vec<HighlightTextSpan>, snippets_loaded: bool) -> Element {
     rsx! {
         div {
             style: "
@@ -151,7 +203,11 @@ fn CardTitleSection(highlight_filenames_spans: Vec<HighlightTextSpan>) -> Elemen
                 white-space: nowrap;
                 min-width: 0;
             ",
-            {render_highlight_text_span(highlight_filenames_spans)}
+            if snippets_loaded {
+                {render_highlight_text_span(highlight_filenames_spans)}
+            } else {
+                "{title}"
+            }
         }
     }
 }
@@ -198,7 +254,7 @@ fn ComponentNameSection(collection_dataset: String) -> Element {
 }
 
 #[component]
-fn HighlightTextSnippetSection(highlight_text_spans: Vec<HighlightTextSpan>) -> Element {
+fn HighlightTextSnippetSection(highlight_text_spans: Vec<HighlightTextSpan>, snippets_loaded: bool, line_clamp: u32) -> Element {
 
     rsx! {
         div {
@@ -210,22 +266,43 @@ fn HighlightTextSnippetSection(highlight_text_spans: Vec<HighlightTextSpan>) ->
                 color: rgb(0, 0, 0);
                 overflow: hidden;
                 display: -webkit-box;
-                -webkit-line-clamp: 4;
+                -webkit-line-clamp: {line_clamp};
                 -webkit-box-orient: vertical;
                 flex: 1;
                 min-width: 0;
                 letter-spacing: 0.0em;
             ",
-            {render_highlight_text_span(highlight_text_spans)}
+            if snippets_loaded {
+                {render_highlight_text_span(highlight_text_spans)}
+            } else {
+                span {
+                    style: "color: rgba(0, 0, 0, 0.4); font-style: italic;",
+                    "Loading snippet…"
+                }
+            }
         }
     }
 }
 
-fn render_highlight_text_span(spans: Vec<HighlightTextSpan>) -> Element {
+/// Background colors cycled across distinct `term_index` values so each
+/// query term reads as its own color in a multi-term search. Spans with no
+/// term attribution (`term_index: None`) fall back to the original single
+/// highlight color.
+const TERM_HIGHLIGHT_COLORS: [&str; 6] = ["#EB3E014D", "#34C7594D", "#2F8FE84D", "#C94FDB4D", "#E8B3224D", "#3ED6C94D"];
+
+pub(crate) fn highlight_color(term_index: Option<usize>) -> &'static str {
+    match term_index {
+        Some(term_index) => TERM_HIGHLIGHT_COLORS[term_index % TERM_HIGHLIGHT_COLORS.len()],
+        None => TERM_HIGHLIGHT_COLORS[0],
+    }
+}
+
+pub(crate) fn render_highlight_text_span(spans: Vec<HighlightTextSpan>) -> Element {
     let spans = spans.into_iter().map(|i| {
-        let color = if i.is_highlighted { "#EB3E014D" } else { "transparent" };
+        let color = if i.is_highlighted { highlight_color(i.term_index) } else { "transparent" };
         rsx! {
             span {
+                title: i.term_text.clone().unwrap_or_default(),
                 style: "background-color: {color}; color: rgb(0, 0, 0);",
                 "{i.text}"
             }
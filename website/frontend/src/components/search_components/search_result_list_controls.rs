@@ -1,11 +1,12 @@
 //! Controls for search result list settings.
 
-use common::search_const::{MAX_PAGINATION_DOCUMENT_LIMIT, PAGE_SIZE};
+use common::{search_const::{MAX_PAGINATION_DOCUMENT_LIMIT, PAGE_SIZE}, search_export::ExportFormat};
 use dioxus::prelude::*;
-use dioxus_free_icons::{Icon, icons::md_navigation_icons::{MdArrowBack, MdArrowDownward, MdArrowForward, MdArrowLeft, MdArrowRight, MdArrowUpward}};
+use dioxus_free_icons::{Icon, icons::md_action_icons::MdViewComfy, icons::md_navigation_icons::{MdArrowBack, MdArrowDownward, MdArrowForward, MdArrowLeft, MdArrowRight, MdArrowUpward}, icons::md_toggle_icons::{MdCheckBox, MdCheckBoxOutlineBlank}};
 use dioxus_primitives::{ContentAlign, ContentSide};
+use futures::StreamExt;
 
-use crate::{components::hover_card::{HoverCard, HoverCardContent, HoverCardTrigger}, components::search_components::search_panel_left_view::SearchResultsState};
+use crate::{api::search_api::export_search_results, components::hover_card::{HoverCard, HoverCardContent, HoverCardTrigger}, components::search_components::search_panel_left_view::SearchResultsState, data_definitions::result_card_display_settings::ResultCardDensity, pages::search_page::ResultCardDisplaySettingsControl};
 
 #[component]
 pub fn SearchResultListControls() -> Element {
@@ -30,12 +31,343 @@ pub fn SearchResultListControls() -> Element {
                 style: "
                 flex-grow: 1;"
             }
+            // bulk export of the whole result set
+            ExportButton {}
+            // display settings (density, visible fields)
+            DisplaySettingsMenu {}
             // pagination buttons
             PaginationControls {}
         }
     }
 }
 
+/// Triggers `export_search_results` over the whole result set (not just the
+/// current page) in one of [`ExportFormat`]'s three shapes, and downloads
+/// the finished file client-side via a `Blob`/`<a download>` trigger.
+#[component]
+fn ExportButton() -> Element {
+    let search_results_state = use_context::<SearchResultsState>();
+    let mut is_expanded = use_signal(|| false);
+    let mut export_progress = use_signal(|| None::<String>);
+
+    let start_export = move |format: ExportFormat| {
+        let Some(Ok(search_result)) = search_results_state.search_result.read().clone() else { return };
+        is_expanded.set(false);
+        export_progress.set(Some("Starting export…".to_string()));
+        spawn(async move {
+            let mut stream = match export_search_results(search_result.query, format).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    dioxus::logger::tracing::error!("export_search_results failed: {e}");
+                    export_progress.set(Some(format!("! export failed: {e}")));
+                    return;
+                }
+            };
+
+            let unit = if format == ExportFormat::Zip { "chunks" } else { "documents" };
+            let mut lines = Vec::new();
+            while let Some(line) = stream.next().await {
+                match line {
+                    Ok(line) => {
+                        lines.push(line);
+                        export_progress.set(Some(format!("Exported {} {unit}…", lines.len())));
+                    }
+                    Err(e) => dioxus::logger::tracing::error!("export_search_results stream error: {e}"),
+                }
+            }
+
+            let (content, encoding) = match format {
+                ExportFormat::Zip => (lines.concat().replace('\n', ""), "base64"),
+                ExportFormat::Csv | ExportFormat::Ndjson => (lines.concat(), "text"),
+            };
+            let mime = match format {
+                ExportFormat::Csv => "text/csv",
+                ExportFormat::Ndjson => "application/x-ndjson",
+                ExportFormat::Zip => "application/zip",
+            };
+            let filename = format!("hoover4-search-export.{}", format.file_extension());
+
+            // Content is handed to JS via `eval.send`/`dioxus.recv` rather
+            // than interpolated into the script string, so an arbitrary
+            // exported payload can never break out of the JS literal it
+            // would otherwise be embedded in.
+            let mut trigger_download = document::eval(
+                r#"
+                const payload = await dioxus.recv();
+                let blob;
+                if (payload.encoding === "base64") {
+                    const binary = atob(payload.content);
+                    const bytes = new Uint8Array(binary.length);
+                    for (let i = 0; i < binary.length; i++) { bytes[i] = binary.charCodeAt(i); }
+                    blob = new Blob([bytes], { type: payload.mime });
+                } else {
+                    blob = new Blob([payload.content], { type: payload.mime });
+                }
+                const url = URL.createObjectURL(blob);
+                const a = document.createElement("a");
+                a.href = url;
+                a.download = payload.filename;
+                document.body.appendChild(a);
+                a.click();
+                a.remove();
+                URL.revokeObjectURL(url);
+                "#,
+            );
+            let _ = trigger_download.send(serde_json::json!({
+                "content": content,
+                "mime": mime,
+                "encoding": encoding,
+                "filename": filename,
+            }));
+
+            export_progress.set(None);
+        });
+    };
+
+    rsx! {
+        div {
+            style: "position: relative; display: flex; align-items: center; gap: 8px;",
+            button {
+                style: "
+                    height: 32px;
+                    padding: 0 12px;
+                    background: white;
+                    border-radius: 8px;
+                    border: none;
+                    box-shadow: 0 2px 4px 0 rgba(0, 0, 0, 0.16);
+                    cursor: pointer;
+                    font-size: 14px;
+                    color: rgba(0, 0, 0, 0.7);
+                ",
+                disabled: export_progress.read().is_some(),
+                onclick: move |_e| {
+                    _e.stop_propagation();
+                    *is_expanded.write() ^= true;
+                },
+                "Export"
+            }
+            if let Some(progress) = export_progress.read().clone() {
+                span { style: "font-size: 13px; color: rgba(0, 0, 0, 0.5);", "{progress}" }
+            }
+            if is_expanded() {
+                div {
+                    style: "
+                        position: fixed;
+                        top: 0px;
+                        left: 0px;
+                        width: 100vw;
+                        height: 100vh;
+                        z-index: 1000;
+                    ",
+                    onclick: move |_e| {
+                        _e.stop_propagation();
+                        *is_expanded.write() = false;
+                    },
+                }
+                div {
+                    style: "
+                        position: absolute;
+                        top: 38px;
+                        left: 0px;
+                        width: 200px;
+                        background-color: white;
+                        border: 1px solid rgba(0, 0, 0, 0.5);
+                        box-shadow: 0 0 10px 0 rgba(0, 0, 0, 0.5);
+                        border-radius: 4px;
+                        padding: 6px;
+                        gap: 2px;
+                        z-index: 1001;
+                        display: flex;
+                        flex-direction: column;
+                        font-size: 15px;
+                    ",
+                    onclick: move |_e| _e.stop_propagation(),
+                    ExportFormatOption { format: ExportFormat::Csv, on_select: start_export }
+                    ExportFormatOption { format: ExportFormat::Ndjson, on_select: start_export }
+                    ExportFormatOption { format: ExportFormat::Zip, on_select: start_export }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ExportFormatOption(format: ExportFormat, on_select: Callback<ExportFormat>) -> Element {
+    rsx! {
+        div {
+            style: "
+                padding: 6px 8px;
+                cursor: pointer;
+                border-radius: 4px;
+            ",
+            class: "hoover4-hover-shadow-background",
+            onclick: move |_| on_select.call(format),
+            "{format.label()}"
+        }
+    }
+}
+
+#[component]
+fn DisplaySettingsMenu() -> Element {
+    let display_settings = use_context::<ResultCardDisplaySettingsControl>();
+    let settings = display_settings.settings;
+    let mut is_expanded = use_signal(|| false);
+
+    rsx! {
+        div {
+            style: "position: relative;",
+            button {
+                style: "
+                    width: 32px;
+                    height: 32px;
+                    background: white;
+                    border-radius: 8px;
+                    border: none;
+                    padding: 4px;
+                    box-shadow: 0 2px 4px 0 rgba(0, 0, 0, 0.16);
+                    cursor: pointer;
+                ",
+                onclick: move |_e| {
+                    _e.stop_propagation();
+                    *is_expanded.write() ^= true;
+                },
+                Icon { icon: MdViewComfy, style: "width: 24px; height: 24px; color: rgba(0, 0, 0, 0.7);" }
+            }
+            if is_expanded() {
+                div {
+                    style: "
+                        position: fixed;
+                        top: 0px;
+                        left: 0px;
+                        width: 100vw;
+                        height: 100vh;
+                        background: rgba(0, 0, 0, 0.05);
+                        z-index: 1000;
+                    ",
+                    onclick: move |_e| {
+                        _e.stop_propagation();
+                        *is_expanded.write() = false;
+                    },
+                }
+                div {
+                    style: "
+                        position: absolute;
+                        top: 38px;
+                        right: 0px;
+                        width: 260px;
+                        background-color: white;
+                        border: 1px solid rgba(0, 0, 0, 0.5);
+                        box-shadow: 0 0 10px 0 rgba(0, 0, 0, 0.5);
+                        border-radius: 4px;
+                        padding: 10px;
+                        gap: 6px;
+                        z-index: 1001;
+                        display: flex;
+                        flex-direction: column;
+                        font-size: 15px;
+                    ",
+                    onclick: move |_e| _e.stop_propagation(),
+
+                    span { style: "font-weight: 500; color: rgba(0, 0, 0, 0.6);", "Card density" }
+                    for density in ResultCardDensity::ALL {
+                        DensityOption { density }
+                    }
+                    div { style: "width: 100%; border-bottom: 1px solid rgba(0, 0, 0, 0.2); margin: 4px 0;" }
+                    span { style: "font-weight: 500; color: rgba(0, 0, 0, 0.6);", "Visible fields" }
+                    FieldToggle {
+                        label: "Collection name",
+                        checked: settings.read().fields.show_collection_name,
+                        onclick: move |_| {
+                            let mut fields = settings.read().fields;
+                            fields.show_collection_name = !fields.show_collection_name;
+                            display_settings.set_fields.call(fields);
+                        },
+                    }
+                    FieldToggle {
+                        label: "File type icon",
+                        checked: settings.read().fields.show_file_type_icon,
+                        onclick: move |_| {
+                            let mut fields = settings.read().fields;
+                            fields.show_file_type_icon = !fields.show_file_type_icon;
+                            display_settings.set_fields.call(fields);
+                        },
+                    }
+                    FieldToggle {
+                        label: "Item index",
+                        checked: settings.read().fields.show_item_index,
+                        onclick: move |_| {
+                            let mut fields = settings.read().fields;
+                            fields.show_item_index = !fields.show_item_index;
+                            display_settings.set_fields.call(fields);
+                        },
+                    }
+                    FieldToggle {
+                        label: "Text snippet",
+                        checked: settings.read().fields.show_snippet,
+                        onclick: move |_| {
+                            let mut fields = settings.read().fields;
+                            fields.show_snippet = !fields.show_snippet;
+                            display_settings.set_fields.call(fields);
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DensityOption(density: ResultCardDensity) -> Element {
+    let display_settings = use_context::<ResultCardDisplaySettingsControl>();
+    let is_selected = display_settings.settings.read().density == density;
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                flex-direction: row;
+                align-items: center;
+                gap: 8px;
+                padding: 4px 6px;
+                cursor: pointer;
+                border-radius: 4px;
+            ",
+            class: "hoover4-hover-shadow-background",
+            onclick: move |_| display_settings.set_density.call(density),
+            if is_selected {
+                Icon { icon: MdCheckBox, style: "width: 18px; height: 18px; color: rgb(28, 33, 45);" }
+            } else {
+                Icon { icon: MdCheckBoxOutlineBlank, style: "width: 18px; height: 18px; color: rgba(0, 0, 0, 0.4);" }
+            }
+            "{density.label()}"
+        }
+    }
+}
+
+#[component]
+fn FieldToggle(label: &'static str, checked: bool, onclick: EventHandler<()>) -> Element {
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                flex-direction: row;
+                align-items: center;
+                gap: 8px;
+                padding: 4px 6px;
+                cursor: pointer;
+                border-radius: 4px;
+            ",
+            class: "hoover4-hover-shadow-background",
+            onclick: move |_| onclick.call(()),
+            if checked {
+                Icon { icon: MdCheckBox, style: "width: 18px; height: 18px; color: rgb(28, 33, 45);" }
+            } else {
+                Icon { icon: MdCheckBoxOutlineBlank, style: "width: 18px; height: 18px; color: rgba(0, 0, 0, 0.4);" }
+            }
+            "{label}"
+        }
+    }
+}
+
 
 #[component]
 fn PaginationControls() -> Element {
@@ -97,50 +429,11 @@ fn ControlNextPrevDocument() -> Element {
         idx.map(|idx| idx < *hit_count.read()).unwrap_or(false)
     });
 
-    let go_previous = move |_e| {
-        let current_list_position = current_list_position();
-        let Some(current_list_position) = current_list_position else {
-            return;
-        };
-        if current_list_position == 0 {
-            // fetch previous page and id
-            let search_result = search_results_state.search_result.read();
-            let search_result = search_result.as_ref();
-            if let Some(Ok(search_result)) = search_result {
-                if let Some(prev_hash) = search_result.prev_hash.clone() {
-                    // fetch previous page and id
-                    if *search_results_state.current_search_result_page.read() > 0 {
-                        search_results_state.set_selected_result_hash_and_page.call((Some(prev_hash.clone()), *search_results_state.current_search_result_page.read() - 1));
-                    }
-                }
-            }
-        } else {
-            let result_hashes = result_hashes();
-            let prev_hash = &result_hashes[current_list_position as usize - 1];
-            search_results_state.set_selected_result_hash.call(Some(prev_hash.clone()));
-        }
-    };
-
-    let go_next = move |_e| {
-        let current_list_position = current_list_position();
-        let Some(current_list_position) = current_list_position else {
-            return;
-        };
-        let result_hashes = result_hashes();
-        if current_list_position == result_hashes.len() as u64 - 1 {
-            let search_result = search_results_state.search_result.read();
-        let search_result = search_result.as_ref();
-        if let Some(Ok(next_hash)) = search_result {
-            if let Some(next_hash) = next_hash.next_hash.clone() {
-                // fetch next page and id
-                search_results_state.set_selected_result_hash_and_page.call((Some(next_hash.clone()), *search_results_state.current_search_result_page.read() + 1));
-            }
-        }
-        } else {
-            let next_hash = &result_hashes[current_list_position as usize + 1];
-            search_results_state.set_selected_result_hash.call(Some(next_hash.clone()));
-        }
-    };
+    // Shared with the global `j`/`k` keyboard shortcuts registered in
+    // `SearchPanelLeftView`, so buttons and shortcuts advance/retreat
+    // through exactly the same logic.
+    let go_previous = move |_e| search_results_state.go_previous_result.call(());
+    let go_next = move |_e| search_results_state.go_next_result.call(());
     rsx! {
         // prev result
         NavigationButton {
@@ -0,0 +1,52 @@
+//! Shared "did you mean" dropdown rendered under a search box input,
+//! fed by [`crate::api::search_api::search_suggestions`].
+
+use common::search_suggestions::Suggestion;
+use dioxus::prelude::*;
+
+/// Replaces the last whitespace-delimited token of `query` with `term`,
+/// leaving any already-typed tokens before it untouched.
+pub fn complete_last_token(query: &str, term: &str) -> String {
+    let mut tokens: Vec<&str> = query.split_whitespace().collect();
+    match tokens.last_mut() {
+        Some(last) => { *last = term; tokens.join(" ") }
+        None => term.to_string(),
+    }
+}
+
+/// Renders `suggestions` as a clickable/hoverable list. `highlighted` is the
+/// index moved by arrow keys in the owning input's `onkeydown`; `on_select`
+/// fires with the chosen index on click. Enter is not handled here: the
+/// owning input's `onkeydown` picks the highlighted entry itself.
+#[component]
+pub fn SearchSuggestionsDropdown(suggestions: Vec<Suggestion>, highlighted: usize, on_select: Callback<usize>) -> Element {
+    if suggestions.is_empty() {
+        return rsx! {};
+    }
+    rsx! {
+        ul {
+            style: "
+                list-style: none;
+                margin: 0;
+                padding: 4px 0;
+            ",
+            for (index, suggestion) in suggestions.iter().enumerate() {
+                li {
+                    key: "{suggestion.term}",
+                    style: "
+                        padding: 6px 16px;
+                        cursor: pointer;
+                        font-size: 15px;
+                        color: #111827;
+                        background: {if index == highlighted { \"rgba(79, 70, 229, 0.1)\" } else { \"transparent\" }};
+                    ",
+                    onmousedown: move |e: Event<MouseData>| {
+                        e.prevent_default();
+                        on_select.call(index);
+                    },
+                    "{suggestion.term}"
+                }
+            }
+        }
+    }
+}
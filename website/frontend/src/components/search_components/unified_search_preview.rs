@@ -0,0 +1,144 @@
+//! Live, per-provider preview of a query shown below the search top-bar
+//! input: content, filename and metadata matches fan out in parallel and
+//! render as collapsible, count-labelled sections, so the user doesn't have
+//! to pick a search mode before seeing anything.
+
+use common::{search_query::SearchQuery, search_result::SearchResultDocumentItem, unified_search::{UnifiedSearchProvider, UnifiedSearchResultGroup}};
+use dioxus::prelude::*;
+use dioxus_free_icons::{Icon, icons::md_navigation_icons::{MdArrowDropDown, MdArrowDropUp}};
+
+use crate::{api::search_api::search_unified, components::{error_boundary::ComponentErrorDisplay, search_components::search_result_item_card::render_highlight_text_span, suspend_boundary::LoadingIndicator}, routes::Route};
+
+#[component]
+pub fn UnifiedSearchPreview(search_query: ReadSignal<SearchQuery>) -> Element {
+    let query_string = use_memo(move || search_query.read().query_string.trim().to_string());
+    if query_string.read().is_empty() {
+        return rsx! {};
+    }
+
+    let mut preview_res = use_resource(move || search_unified(search_query.read().clone()));
+    use_effect(move || {
+        let _q = search_query.read().clone();
+        preview_res.clear();
+        preview_res.restart();
+    });
+
+    let groups = match preview_res.read().clone() {
+        Some(Ok(result)) => result.groups,
+        Some(Err(e)) => return rsx! {
+            div {
+                style: "padding: 12px;",
+                ComponentErrorDisplay { error_txt: format!("{:#?}", e) }
+            }
+        },
+        None => return rsx! {
+            div { style: "padding: 12px;", LoadingIndicator {} }
+        },
+    };
+
+    if groups.iter().all(|g| g.total_count == 0) {
+        return rsx! {
+            div {
+                style: "padding: 16px; color: rgba(0, 0, 0, 0.5);",
+                "No matches for "
+                i { b { "{query_string}" } }
+            }
+        };
+    }
+
+    rsx! {
+        div {
+            style: "
+                display: flex;
+                flex-direction: column;
+                max-height: 70vh;
+                overflow-y: auto;
+            ",
+            for group in groups {
+                div {
+                    key: "{group.provider:?}",
+                    UnifiedSearchProviderSection { group }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn UnifiedSearchProviderSection(group: UnifiedSearchResultGroup) -> Element {
+    let mut is_expanded = use_signal(|| true);
+    if group.total_count == 0 {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            style: "border-top: 1px solid rgba(0, 0, 0, 0.1);",
+            div {
+                style: "
+                    display: flex;
+                    align-items: center;
+                    justify-content: space-between;
+                    padding: 10px 16px;
+                    cursor: pointer;
+                    font-size: 14px;
+                    font-weight: 500;
+                    color: rgba(0, 0, 0, 0.7);
+                ",
+                onclick: move |_| { *is_expanded.write() ^= true; },
+                span { "{group.provider.label()} ({group.total_count})" }
+                if is_expanded() {
+                    Icon { icon: MdArrowDropUp, style: "width: 20px; height: 20px;" }
+                } else {
+                    Icon { icon: MdArrowDropDown, style: "width: 20px; height: 20px;" }
+                }
+            }
+            if is_expanded() {
+                for item in group.results {
+                    div {
+                        key: "{item.collection_dataset}-{item.file_hash}",
+                        UnifiedSearchPreviewItem { item }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn UnifiedSearchPreviewItem(item: SearchResultDocumentItem) -> Element {
+    rsx! {
+        Link {
+            to: Route::view_document_page(item.document_identifier()),
+            style: "
+                display: block;
+                padding: 8px 16px;
+                text-decoration: none;
+                color: inherit;
+            ",
+            class: "hoover4-hover-shadow-background",
+            div {
+                style: "
+                    font-size: 15px;
+                    overflow: hidden;
+                    text-overflow: ellipsis;
+                    white-space: nowrap;
+                ",
+                {render_highlight_text_span(item.highlight_filenames_spans)}
+            }
+            if !item.highlight_text_spans.is_empty() {
+                div {
+                    style: "
+                        font-size: 13px;
+                        color: rgba(0, 0, 0, 0.6);
+                        overflow: hidden;
+                        display: -webkit-box;
+                        -webkit-line-clamp: 2;
+                        -webkit-box-orient: vertical;
+                    ",
+                    {render_highlight_text_span(item.highlight_text_spans)}
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,46 @@
 //! State definitions for the document viewer.
 
+use common::code_highlight::CodeHighlightTheme;
+use common::search_const::DEFAULT_SNIPPET_AROUND;
 use serde::{Deserialize, Serialize};
 
+/// Default number of words kept on each side of a highlighted hit when
+/// cropping search snippets in the text viewer.
+pub const DEFAULT_CROP_RADIUS: u32 = 40;
+
+/// Max words Manticore keeps per fragment when `snippet_around` is enabled,
+/// matching `document_text_highlight_options`'s `snippet_limit` parameter.
+pub const DEFAULT_SNIPPET_LIMIT: u32 = 200;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DocViewerState {
     pub find_query: String,
     pub selected_text_extracted_by: Option<String>,
-    pub selected_text_page: u32,
+    /// Pins the preview to a specific page instead of the auto-selected best
+    /// source, e.g. when deep-linking in from a search result snippet whose
+    /// match was found on a known page. `None` leaves source/page selection
+    /// up to the usual hit-count ranking.
+    pub selected_text_page: Option<u32>,
+    pub crop_radius: u32,
+    /// Words of context Manticore keeps on each side of a match when
+    /// fetching the current page's text (the `HIGHLIGHT()` `around` option).
+    /// `0` keeps the default whole-page fetch, cropped client-side to
+    /// `crop_radius` instead; the "Focused snippet" toggle in
+    /// `PreviewSubtitleBar` is the only thing that sets this above `0`.
+    pub snippet_around: u32,
+    /// Highlighted-hit index (`HighlightTextSpan::index`) to scroll straight
+    /// to on load, e.g. the top match from a search result snippet. `None`
+    /// starts at the first hit, same as opening the document fresh.
+    pub match_index: Option<u64>,
+    /// Light/dark color scheme for `CodeDataViewer`, so a theme switch
+    /// survives navigating away and back.
+    pub code_theme: CodeHighlightTheme,
+    /// Top-most visible page in `PDFDataViewer`'s continuous-scroll PDF
+    /// view. `None` starts at the first page.
+    pub pdf_page_index: Option<u32>,
+    /// Zoom multiplier applied on top of `PDFDataViewer`'s auto-fit
+    /// `scale_factor`. `None` means the default 1.0 (no extra zoom).
+    pub pdf_zoom_level: Option<f32>,
 }
 
 impl DocViewerState {
@@ -14,7 +48,40 @@ impl DocViewerState {
         Self {
             find_query,
             selected_text_extracted_by: None,
-            selected_text_page: 0,
+            selected_text_page: None,
+            crop_radius: DEFAULT_CROP_RADIUS,
+            snippet_around: 0,
+            match_index: None,
+            code_theme: CodeHighlightTheme::default(),
+            pdf_page_index: None,
+            pdf_zoom_level: None,
+        }
+    }
+
+    /// Builds the state for deep-linking straight to a search result's top
+    /// match: pins the page the snippet was drawn from and the hit to
+    /// scroll to, instead of falling back to the auto-selected best source.
+    pub fn from_search_match(find_query: String, page_id: Option<u32>, match_index: Option<u64>) -> Self {
+        Self {
+            find_query,
+            selected_text_extracted_by: None,
+            selected_text_page: page_id,
+            crop_radius: DEFAULT_CROP_RADIUS,
+            snippet_around: 0,
+            match_index,
+            code_theme: CodeHighlightTheme::default(),
+            pdf_page_index: None,
+            pdf_zoom_level: None,
+        }
+    }
+
+    /// Flips between the whole-page fetch (`snippet_around == 0`) and a
+    /// focused contextual snippet (`DEFAULT_SNIPPET_AROUND` words either
+    /// side of each match), leaving every other field untouched.
+    pub fn with_snippet_around_toggled(&self) -> Self {
+        Self {
+            snippet_around: if self.snippet_around == 0 { DEFAULT_SNIPPET_AROUND } else { 0 },
+            ..self.clone()
         }
     }
 }
\ No newline at end of file
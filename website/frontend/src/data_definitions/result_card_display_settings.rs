@@ -0,0 +1,96 @@
+//! Persisted, user-configurable display preferences for search result cards.
+
+use serde::{Deserialize, Serialize};
+
+const LOCAL_STORAGE_KEY: &str = "hoover4_result_card_display_settings";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ResultCardDensity {
+    Compact,
+    Comfortable,
+    Detailed,
+}
+
+impl ResultCardDensity {
+    /// Overall card height, in pixels, for this density.
+    pub fn card_height_px(&self) -> u32 {
+        match self {
+            ResultCardDensity::Compact => 96,
+            ResultCardDensity::Comfortable => 148,
+            ResultCardDensity::Detailed => 220,
+        }
+    }
+
+    /// Number of lines the highlighted snippet is clamped to.
+    pub fn snippet_line_clamp(&self) -> u32 {
+        match self {
+            ResultCardDensity::Compact => 1,
+            ResultCardDensity::Comfortable => 4,
+            ResultCardDensity::Detailed => 8,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResultCardDensity::Compact => "Compact",
+            ResultCardDensity::Comfortable => "Comfortable",
+            ResultCardDensity::Detailed => "Detailed",
+        }
+    }
+
+    pub const ALL: [ResultCardDensity; 3] = [
+        ResultCardDensity::Compact,
+        ResultCardDensity::Comfortable,
+        ResultCardDensity::Detailed,
+    ];
+}
+
+impl Default for ResultCardDensity {
+    fn default() -> Self {
+        ResultCardDensity::Comfortable
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResultCardFieldVisibility {
+    pub show_collection_name: bool,
+    pub show_file_type_icon: bool,
+    pub show_item_index: bool,
+    pub show_snippet: bool,
+}
+
+impl Default for ResultCardFieldVisibility {
+    fn default() -> Self {
+        ResultCardFieldVisibility {
+            show_collection_name: true,
+            show_file_type_icon: true,
+            show_item_index: true,
+            show_snippet: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub struct ResultCardDisplaySettings {
+    pub density: ResultCardDensity,
+    pub fields: ResultCardFieldVisibility,
+}
+
+impl ResultCardDisplaySettings {
+    /// Loads the persisted settings from local storage, falling back to
+    /// defaults if nothing was saved yet or the stored value can't be parsed.
+    pub fn load() -> Self {
+        let Some(window) = web_sys::window() else { return Self::default() };
+        let Ok(Some(storage)) = window.local_storage() else { return Self::default() };
+        let Ok(Some(raw)) = storage.get_item(LOCAL_STORAGE_KEY) else { return Self::default() };
+        serde_json::from_str(&raw).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let Some(window) = web_sys::window() else { return };
+        let Ok(Some(storage)) = window.local_storage() else { return };
+        if let Ok(raw) = serde_json::to_string(self) {
+            let _ = storage.set_item(LOCAL_STORAGE_KEY, &raw);
+        }
+    }
+}
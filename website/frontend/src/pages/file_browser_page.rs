@@ -1,15 +1,95 @@
 //! File browser page layout and integration.
 
+use common::{search_query::SearchQuery, search_result::DocumentIdentifier};
 use dioxus::prelude::*;
 
+use crate::{
+    components::{document_view_components::doc_preview_for_search::DocumentPreviewForSearchRoot, file_browser_components::{collection_tree::CollectionTree, directory_listing::DirectoryListing, file_browser_nav_state::FileBrowserNavState}, suspend_boundary::SuspendWrapper},
+    data_definitions::doc_viewer_state::DocViewerState,
+    pages::search_page::DocViewerStateControl,
+};
 
 /// File browser page
 #[component]
 pub fn FileBrowserPage() -> Element {
     rsx! {
         Title { "Hoover Search - File Browser" }
-        h1 {
-            "FileBrowserPage"
+        FileBrowserPageRootComponent {}
+    }
+}
+
+#[component]
+fn FileBrowserPageRootComponent() -> Element {
+    let mut current_collection = use_signal(|| None::<String>);
+    let mut current_path_segments = use_signal(|| Vec::<String>::new());
+    let mut selected_result_hash = use_signal(|| None::<DocumentIdentifier>);
+
+    use_context_provider(move || FileBrowserNavState {
+        current_collection: current_collection.into(),
+        current_path_segments: current_path_segments.into(),
+        selected_result_hash: selected_result_hash.into(),
+        open_directory: Callback::new(move |(collection_dataset, path_segments): (String, Vec<String>)| {
+            current_collection.set(Some(collection_dataset));
+            current_path_segments.set(path_segments);
+        }),
+        select_file: Callback::new(move |file_hash: String| {
+            let Some(collection_dataset) = current_collection.read().clone() else { return };
+            selected_result_hash.set(Some(DocumentIdentifier { collection_dataset, file_hash }));
+        }),
+    });
+
+    let doc_viewer_state = use_signal(|| None::<DocViewerState>);
+    use_context_provider(move || DocViewerStateControl {
+        doc_viewer_state: doc_viewer_state.into(),
+        set_doc_viewer_state: Callback::new(move |state: DocViewerState| {
+            let mut doc_viewer_state = doc_viewer_state;
+            doc_viewer_state.set(Some(state));
+        }),
+    });
+
+    let query = use_signal(SearchQuery::default);
+
+    rsx! {
+        div {
+            id: "x-file-browser-page-root-component",
+            style: "
+                height: 100%;
+                width: 100%;
+                display: flex;
+                flex-direction: row;
+            ",
+            div {
+                id: "x-file-browser-collection-tree-panel",
+                style: "
+                    height: 100%;
+                    background-color: #ECEEF2;
+                    min-width: 220px;
+                    width: 20%;
+                    overflow-y: auto;
+                    flex-shrink: 0;
+                ",
+                CollectionTree {}
+            }
+            div {
+                id: "x-file-browser-listing-panel",
+                style: "
+                    height: 100%;
+                    min-width: 400px;
+                    width: 45%;
+                    border-left: 1px solid rgba(0, 0, 0, 0.2);
+                    border-right: 1px solid rgba(0, 0, 0, 0.2);
+                ",
+                DirectoryListing {}
+            }
+            div {
+                id: "x-file-browser-preview-panel",
+                style: "
+                    height: 100%;
+                    min-width: 300px;
+                    width: 35%;
+                ",
+                SuspendWrapper { DocumentPreviewForSearchRoot { query: query.into(), selected_result_hash: selected_result_hash.into() } }
+            }
         }
     }
-}
\ No newline at end of file
+}
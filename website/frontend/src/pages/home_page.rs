@@ -1,9 +1,15 @@
+use std::str::FromStr;
+
 use dioxus::prelude::*;
 use dioxus_free_icons::icons::md_action_icons::MdSearch;
 use dioxus_free_icons::icons::md_communication_icons::MdChat;
 use dioxus_free_icons::Icon;
 
+use common::saved_search::SavedSearch;
 use common::search_query::SearchQuery;
+use crate::api::saved_searches_api::{delete_saved_search, list_saved_searches};
+use crate::api::search_api::{search_for_results_hit_count, search_suggestions};
+use crate::components::search_components::search_suggestions_dropdown::{SearchSuggestionsDropdown, complete_last_token};
 use crate::data_definitions::url_param::UrlParam;
 use crate::routes::Route;
 
@@ -44,6 +50,16 @@ pub fn HomePage() -> Element {
                 AiChatCard {}
             }
 
+            // Saved Searches Row
+            div {
+                style: "
+                    display:flex;
+                    flex-direction: row;
+                    gap: 20px;
+                ",
+                SavedSearchesCard {}
+            }
+
             // Feedback Row
             div {
                 style: "
@@ -157,40 +173,103 @@ fn TextSearchCard() -> Element {
 fn SearchCardInput() -> Element {
     let n2 = navigator();
     let mut search_q = use_signal(|| "".to_string());
+    let mut highlighted = use_signal(|| 0usize);
+
+    // ~150ms debounce: `document::eval` sleeps before the fetch, and
+    // `use_resource` drops the previous future as soon as `search_q` changes
+    // again, so only the last keystroke's suggestions actually land.
+    let suggestions_res = use_resource(move || {
+        let prefix = search_q.read().clone();
+        async move {
+            let mut timer = document::eval("await new Promise(r => setTimeout(r, 150)); dioxus.send(true);");
+            let _ = timer.recv::<bool>().await;
+            search_suggestions(prefix, 8).await
+        }
+    });
+    let suggestions = suggestions_res.read().as_ref().and_then(|r| r.as_ref().ok()).cloned().unwrap_or_default();
+    use_effect(move || {
+        let _ = search_q.read();
+        highlighted.set(0);
+    });
+
+    let do_search = move |query_string: String| {
+        let search_q = SearchQuery { query_string, ..Default::default() };
+        n2.push(Route::search_page_from_query(search_q));
+    };
+    let select_suggestion = move |index: usize| {
+        if let Some(s) = suggestions.get(index) {
+            search_q.set(complete_last_token(&search_q.read(), &s.term));
+        }
+    };
+
     rsx! {
         div {
-            style: "
-                display:flex;
-                align-items:center;
-                gap: 10px;
-                background-color: white;
-                border-radius: 9999px;
-                padding: 10px 14px;
-                height: 42px;
-                color: #111827;
-            ",
-            Icon { icon: MdSearch, style: "width: 20px; height: 20px; color:#6B7280;" }
-            input {
-                r#type: "text",
-                placeholder: "Search in knowledgebase",
+            style: "position: relative; width: 100%;",
+            div {
                 style: "
-                    flex:1;
-                    border: none;
-                    outline: none;
-                    background: transparent;
+                    display:flex;
+                    align-items:center;
+                    gap: 10px;
+                    background-color: white;
+                    border-radius: 9999px;
+                    padding: 10px 14px;
+                    height: 42px;
                     color: #111827;
-                    font-size: 14px;
                 ",
-                oninput: move |e| {
-                    *search_q.write() = e.value();
-                },
-                onkeypress: move |e| {
-                    if e.key() == Key::Enter {
-                        e.prevent_default();
-                        let search_q = SearchQuery { query_string: search_q.read().clone(), ..Default::default() };
-                        n2.push( Route::search_page_from_query(search_q) );
-                    }
-                },
+                Icon { icon: MdSearch, style: "width: 20px; height: 20px; color:#6B7280;" }
+                input {
+                    r#type: "text",
+                    placeholder: "Search in knowledgebase",
+                    style: "
+                        flex:1;
+                        border: none;
+                        outline: none;
+                        background: transparent;
+                        color: #111827;
+                        font-size: 14px;
+                    ",
+                    value: "{search_q}",
+                    oninput: move |e| {
+                        *search_q.write() = e.value();
+                    },
+                    onkeydown: move |e: Event<KeyboardData>| {
+                        match e.key() {
+                            Key::ArrowDown if !suggestions.is_empty() => {
+                                e.prevent_default();
+                                highlighted.set((highlighted() + 1) % suggestions.len());
+                            }
+                            Key::ArrowUp if !suggestions.is_empty() => {
+                                e.prevent_default();
+                                highlighted.set((highlighted() + suggestions.len() - 1) % suggestions.len());
+                            }
+                            Key::Enter => {
+                                e.prevent_default();
+                                if suggestions.is_empty() {
+                                    do_search(search_q.read().clone());
+                                } else {
+                                    select_suggestion(highlighted());
+                                }
+                            }
+                            _ => {}
+                        }
+                    },
+                }
+            }
+            if !suggestions.is_empty() {
+                div {
+                    style: "
+                        position: absolute;
+                        top: calc(100% + 4px);
+                        left: 0;
+                        right: 0;
+                        background: white;
+                        border-radius: 10px;
+                        box-shadow: 0 4px 16px rgba(0, 0, 0, 0.2);
+                        z-index: 10;
+                        overflow: hidden;
+                    ",
+                    SearchSuggestionsDropdown { suggestions: suggestions.clone(), highlighted: highlighted(), on_select: select_suggestion }
+                }
             }
         }
     }
@@ -301,3 +380,112 @@ fn FeedbackCard() -> Element {
         }
     }
 }
+
+#[component]
+fn SavedSearchesCard() -> Element {
+    let mut saved = use_resource(list_saved_searches);
+
+    rsx! {
+        div {
+            id: "x-card-saved-searches",
+            style: "
+                display:flex;
+                flex-direction: column;
+                gap: 10px;
+                width: 100%;
+                border-radius: 16px;
+                padding: 18px;
+                background: white;
+                color: #111827;
+                border: 1px solid #E5E7EB;
+                box-shadow: 0 6px 16px rgba(0,0,0,0.06);
+            ",
+            div { style: "font-size: 20px; font-weight: 500;", "Saved searches" }
+            match saved.read().as_ref() {
+                None => rsx! { div { style: "color: rgba(0,0,0,0.5);", "Loading…" } },
+                Some(Err(e)) => rsx! { div { style: "color: #DC2626;", "! failed to load saved searches: {e}" } },
+                Some(Ok(items)) if items.is_empty() => rsx! {
+                    div { style: "color: rgba(0,0,0,0.5);", "No saved searches yet. Save one from the search bar above a result page." }
+                },
+                Some(Ok(items)) => rsx! {
+                    for item in items.clone() {
+                        SavedSearchRow { key: "{item.id}", saved_search: item, on_deleted: move |_| saved.restart() }
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn SavedSearchRow(saved_search: SavedSearch, on_deleted: Callback<()>) -> Element {
+    let n = navigator();
+    let encoded_query = saved_search.encoded_query.clone();
+    let hit_count = use_resource(move || {
+        let encoded_query = encoded_query.clone();
+        async move {
+            let query = UrlParam::<SearchQuery>::from_str(&encoded_query).ok()?.0;
+            search_for_results_hit_count(query).await.ok()
+        }
+    });
+    let live_hit_count = use_memo(move || hit_count.read().clone().flatten());
+    let grew = use_memo(move || match (live_hit_count(), saved_search.last_hit_count) {
+        (Some(live), Some(last)) => live > last,
+        _ => false,
+    });
+    let hit_count_label = use_memo(move || match live_hit_count() {
+        Some(count) => format!("{count} documents"),
+        None => "…".to_string(),
+    });
+
+    let encoded_query = saved_search.encoded_query.clone();
+    let go_to_search = move |_| {
+        if let Ok(query) = UrlParam::<SearchQuery>::from_str(&encoded_query) {
+            n.push(Route::search_page_from_query(query.0));
+        }
+    };
+    let id = saved_search.id.clone();
+    let do_delete = move |_: ()| {
+        let id = id.clone();
+        spawn(async move {
+            if delete_saved_search(id).await.is_ok() {
+                on_deleted.call(());
+            }
+        });
+    };
+
+    rsx! {
+        div {
+            style: "
+                display:flex;
+                flex-direction: row;
+                align-items: center;
+                gap: 10px;
+                padding: 8px 10px;
+                border-radius: 8px;
+                cursor: pointer;
+            ",
+            class: "hoover4-hover-shadow-background",
+            onclick: go_to_search,
+            div { style: "flex-grow: 1; font-size: 15px;", "{saved_search.name}" }
+            div {
+                style: "font-size: 13px; color: {if grew() { \"#DC2626\" } else { \"rgba(0,0,0,0.5)\" }};",
+                "{hit_count_label}"
+            }
+            button {
+                style: "
+                    border: none;
+                    background: none;
+                    color: rgba(0,0,0,0.4);
+                    cursor: pointer;
+                    font-size: 13px;
+                ",
+                onclick: move |e| {
+                    e.stop_propagation();
+                    do_delete(());
+                },
+                "Delete"
+            }
+        }
+    }
+}
@@ -6,7 +6,7 @@ use common::{search_query::SearchQuery, search_result::{DocumentIdentifier, Sear
 use crate::{
     api::search_api::{search_for_results, search_for_results_hit_count},
     components::{document_view_components::doc_preview_for_search::DocumentPreviewForSearchRoot, error_boundary::ComponentErrorDisplay, search_components::{search_input_top_bar::SearchInputTopBar, search_panel_left_view::SearchPanelLeftView, search_result_item_card::SearchResultItemCard, search_result_list_controls::SearchResultListControls}, suspend_boundary::{LoadingIndicator, SuspendWrapper}},
-    data_definitions::{doc_viewer_state::DocViewerState, url_param::UrlParam}, routes::Route
+    data_definitions::{doc_viewer_state::DocViewerState, result_card_display_settings::{ResultCardDensity, ResultCardDisplaySettings, ResultCardFieldVisibility}, url_param::UrlParam}, routes::Route
 };
 
 
@@ -45,6 +45,13 @@ pub struct DocViewerStateControl {
     pub set_doc_viewer_state: Callback<DocViewerState>,
 }
 
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub struct ResultCardDisplaySettingsControl {
+    pub settings: ReadSignal<ResultCardDisplaySettings>,
+    pub set_density: Callback<ResultCardDensity>,
+    pub set_fields: Callback<ResultCardFieldVisibility>,
+}
+
 #[component]
 fn SearchPageRootComponent(
     query: ReadSignal<SearchQuery>,
@@ -79,6 +86,21 @@ fn SearchPageRootComponent(
         }),
     });
 
+    let mut result_card_display_settings = use_signal(|| ResultCardDisplaySettings::load());
+    use_context_provider(move || ResultCardDisplaySettingsControl {
+        settings: result_card_display_settings.into(),
+        set_density: Callback::new(move |density: ResultCardDensity| {
+            let mut settings = result_card_display_settings.write();
+            settings.density = density;
+            settings.save();
+        }),
+        set_fields: Callback::new(move |fields: ResultCardFieldVisibility| {
+            let mut settings = result_card_display_settings.write();
+            settings.fields = fields;
+            settings.save();
+        }),
+    });
+
     rsx! {
         div {
             id: "x-search-page-root-component",
@@ -31,8 +31,11 @@ pub enum Route {
     },
 
 
-    #[route("/view_document/:document_identifier")]
-    ViewDocumentPage { document_identifier: UrlParam<DocumentIdentifier> },
+    #[route("/view_document/:document_identifier/:doc_viewer_state")]
+    ViewDocumentPage {
+        document_identifier: UrlParam<DocumentIdentifier>,
+        doc_viewer_state: UrlParam<Option<DocViewerState>>,
+    },
 
 
     #[route("/file_browser")]
@@ -52,4 +55,18 @@ impl Route {
             doc_viewer_state: UrlParam::from(None),
         }
     }
+
+    pub fn view_document_page(document_identifier: DocumentIdentifier) -> Self {
+        Self::ViewDocumentPage {
+            document_identifier: UrlParam::from(document_identifier),
+            doc_viewer_state: UrlParam::from(None),
+        }
+    }
+
+    pub fn view_document_page_at_match(document_identifier: DocumentIdentifier, doc_viewer_state: DocViewerState) -> Self {
+        Self::ViewDocumentPage {
+            document_identifier: UrlParam::from(document_identifier),
+            doc_viewer_state: UrlParam::from(Some(doc_viewer_state)),
+        }
+    }
 }
\ No newline at end of file